@@ -0,0 +1,69 @@
+import { createHash } from "crypto";
+import fs from "fs";
+import path from "path";
+import { fileURLToPath } from "url";
+
+const __dirname = path.dirname(fileURLToPath(import.meta.url));
+
+// Downloads every model in the bundled catalog and records the SHA-256 of the
+// published file or archive as its `sha256`, right after its `url`. The file
+// is edited as text to keep its hand-written layout.
+const CATALOG_PATH = path.join(
+  __dirname,
+  "..",
+  "src-tauri",
+  "resources",
+  "models.json",
+);
+
+interface CatalogModel {
+  id: string;
+  url?: string;
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+async function sha256Of(url: string): Promise<string> {
+  const response = await fetch(url);
+  if (!response.ok || !response.body) {
+    throw new Error(`${url} responded with ${response.status}`);
+  }
+  const hash = createHash("sha256");
+  for await (const chunk of response.body) {
+    hash.update(chunk);
+  }
+  return hash.digest("hex");
+}
+
+async function main() {
+  let text = fs.readFileSync(CATALOG_PATH, "utf8");
+  const models: CatalogModel[] = JSON.parse(text).models;
+
+  for (const model of models) {
+    if (!model.url) {
+      continue;
+    }
+    const digest = await sha256Of(model.url);
+    const entry = new RegExp(
+      `("url": "${escapeRegExp(model.url)}",\\n)([ \\t]*)(?:"sha256": "[0-9a-f]*",\\n[ \\t]*)?`,
+    );
+    if (!entry.test(text)) {
+      throw new Error(`Couldn't find the url of ${model.id} in the catalog`);
+    }
+    text = text.replace(
+      entry,
+      (_match, url: string, indent: string) =>
+        `${url}${indent}"sha256": "${digest}",\n${indent}`,
+    );
+    console.log(`${model.id}: ${digest}`);
+  }
+
+  fs.writeFileSync(CATALOG_PATH, text);
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
@@ -1,11 +1,15 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
 use crate::managers::audio::AudioRecordingManager;
-use crate::managers::history::HistoryManager;
+use crate::managers::history::{HistoryManager, TranscriptionMetadata};
+use crate::managers::streaming::StreamingTranscriptionManager;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
 use crate::shortcut;
+use crate::transcription_coordinator::Transcript;
+use crate::transcription_hooks::{run_transcription_hooks, TranscriptionHookPayload};
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils::{
     self, show_processing_overlay, show_recording_overlay, show_transcribing_overlay,
@@ -56,7 +60,10 @@ fn build_system_prompt(prompt_template: &str) -> String {
     prompt_template.replace("${output}", "").trim().to_string()
 }
 
-async fn post_process_transcription(settings: &AppSettings, transcription: &str) -> Option<String> {
+pub(crate) async fn post_process_transcription(
+    settings: &AppSettings,
+    transcription: &str,
+) -> Option<String> {
     let provider = match settings.active_post_process_provider().cloned() {
         Some(provider) => provider,
         None => {
@@ -328,6 +335,13 @@ impl ShortcutAction for TranscribeAction {
         let is_always_on = settings.always_on_microphone;
         debug!("Microphone mode - always_on: {}", is_always_on);
 
+        // Transcribe speech segments while recording so partial text can be shown
+        let stm = app.state::<Arc<StreamingTranscriptionManager>>();
+        let streaming = settings.streaming_transcription_enabled && !rm.is_recording();
+        if streaming {
+            rm.set_segment_sink(Some(stm.start()));
+        }
+
         let mut recording_started = false;
         if is_always_on {
             // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
@@ -370,6 +384,9 @@ impl ShortcutAction for TranscribeAction {
         if recording_started {
             // Dynamically register the cancel shortcut in a separate task to avoid deadlock
             shortcut::register_cancel_shortcut(app);
+        } else if streaming {
+            rm.set_segment_sink(None);
+            stm.cancel();
         }
 
         debug!(
@@ -389,6 +406,7 @@ impl ShortcutAction for TranscribeAction {
         let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
         let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
         let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+        let stm = Arc::clone(&app.state::<Arc<StreamingTranscriptionManager>>());
 
         change_tray_icon(app, TrayIconState::Transcribing);
         show_transcribing_overlay(app);
@@ -420,13 +438,31 @@ impl ShortcutAction for TranscribeAction {
 
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
+
+                // With streaming, only the last segment is still being transcribed
+                let transcription_result = match stm.finish() {
+                    Some(raw) => {
+                        let output = tm.finalize_detailed(raw);
+                        tm.maybe_unload_immediately("transcription");
+                        Ok(output)
+                    }
+                    None => tm.transcribe_detailed(samples),
+                };
+
+                match transcription_result {
+                    Ok(output) => {
+                        let timings = output.timings();
+                        let transcription = output.text;
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
                             transcription
                         );
+                        let mut metadata = TranscriptionMetadata {
+                            transcription_ms: Some(transcription_time.elapsed().as_millis() as i64),
+                            binding_id: Some(binding_id.clone()),
+                            ..output.metadata
+                        };
                         if !transcription.is_empty() {
                             let settings = get_settings(&ah);
                             let mut final_text = transcription.clone();
@@ -445,6 +481,7 @@ impl ShortcutAction for TranscribeAction {
                             if post_process {
                                 show_processing_overlay(&ah);
                             }
+                            let post_process_time = Instant::now();
                             let processed = if post_process {
                                 post_process_transcription(&settings, &final_text).await
                             } else {
@@ -454,6 +491,14 @@ impl ShortcutAction for TranscribeAction {
                                 post_processed_text = Some(processed_text.clone());
                                 final_text = processed_text;
 
+                                metadata.post_process_ms =
+                                    Some(post_process_time.elapsed().as_millis() as i64);
+                                if let Some(provider) = settings.active_post_process_provider() {
+                                    metadata.post_process_model =
+                                        settings.post_process_models.get(&provider.id).cloned();
+                                    metadata.post_process_provider = Some(provider.id.clone());
+                                }
+
                                 // Get the prompt that was used
                                 if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
                                     if let Some(prompt) = settings
@@ -469,7 +514,27 @@ impl ShortcutAction for TranscribeAction {
                                 post_processed_text = Some(final_text.clone());
                             }
 
-                            // Save to history with post-processed text and prompt
+                            if let Some(c) = ah.try_state::<TranscriptionCoordinator>() {
+                                c.notify_transcript(Transcript {
+                                    binding_id: binding_id.clone(),
+                                    text: transcription.clone(),
+                                    post_processed_text: post_processed_text.clone(),
+                                    timestamp: chrono::Utc::now().timestamp(),
+                                });
+                            }
+
+                            metadata.audio_duration_ms = Some(
+                                samples_clone.len() as i64 * 1000 / WHISPER_SAMPLE_RATE as i64,
+                            );
+                            let hook_payload = TranscriptionHookPayload::new(
+                                transcription.clone(),
+                                post_processed_text.clone(),
+                                &metadata,
+                                timings.as_ref().map(|t| t.segments.clone()),
+                            );
+
+                            // Save to history with post-processed text and prompt,
+                            // then hand the result to the configured hooks
                             let hm_clone = Arc::clone(&hm);
                             let transcription_for_history = transcription.clone();
                             tauri::async_runtime::spawn(async move {
@@ -479,11 +544,14 @@ impl ShortcutAction for TranscribeAction {
                                         transcription_for_history,
                                         post_processed_text,
                                         post_process_prompt,
+                                        metadata,
+                                        timings,
                                     )
                                     .await
                                 {
                                     error!("Failed to save transcription to history: {}", e);
                                 }
+                                run_transcription_hooks(&settings, hook_payload);
                             });
 
                             // Paste the final text (either processed or original)
@@ -519,6 +587,7 @@ impl ShortcutAction for TranscribeAction {
                 }
             } else {
                 debug!("No samples retrieved from recording stop");
+                stm.cancel();
                 utils::hide_recording_overlay(&ah);
                 change_tray_icon(&ah, TrayIconState::Idle);
             }
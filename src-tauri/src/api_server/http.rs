@@ -0,0 +1,241 @@
+//! Just enough HTTP/1.1 to serve a handful of local API requests: one request
+//! per connection, bodies sized by `Content-Length`. Headers and body are read
+//! separately so unauthorized uploads are refused before they are received.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Uploads larger than this are rejected before reading the body
+pub const MAX_BODY_BYTES: usize = 256 * 1024 * 1024;
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    /// Empty until filled from [`PendingBody::read`]
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|v| v.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    pub fn json(status: u16, value: &serde_json::Value) -> Self {
+        Self::new(status, "application/json", value.to_string())
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        writer.write_all(&self.body)?;
+        writer.flush()
+    }
+}
+
+/// Why a request couldn't be read, with the status code to answer it with
+#[derive(Debug)]
+pub struct HttpError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<std::io::Error> for HttpError {
+    fn from(e: std::io::Error) -> Self {
+        Self::new(400, format!("Failed to read request: {}", e))
+    }
+}
+
+/// A request whose headers have been read but whose body hasn't
+pub struct PendingBody<R> {
+    reader: BufReader<R>,
+    content_length: usize,
+}
+
+impl<R: Read> PendingBody<R> {
+    /// Reads the body into a buffer that grows as data arrives, so a client
+    /// can't make the server allocate `Content-Length` bytes without sending them
+    pub fn read(mut self) -> Result<Vec<u8>, HttpError> {
+        let mut body = Vec::new();
+        let read = (&mut self.reader)
+            .take(self.content_length as u64)
+            .read_to_end(&mut body)?;
+        if read < self.content_length {
+            return Err(HttpError::new(
+                400,
+                "Request body is shorter than Content-Length",
+            ));
+        }
+        Ok(body)
+    }
+}
+
+/// Reads the request line and headers. The body is left unread, so the
+/// request can be rejected from its headers alone.
+pub fn read_head<R: Read>(reader: R) -> Result<(Request, PendingBody<R>), HttpError> {
+    let mut reader = BufReader::new(reader);
+    let mut header_bytes = 0;
+
+    let request_line = read_line(&mut reader, &mut header_bytes)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(HttpError::new(400, "Malformed request line"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let line = read_line(&mut reader, &mut header_bytes)?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(HttpError::new(400, "Malformed header"));
+        };
+        headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+    }
+
+    if headers
+        .get("transfer-encoding")
+        .is_some_and(|v| !v.eq_ignore_ascii_case("identity"))
+    {
+        return Err(HttpError::new(411, "Chunked uploads are not supported"));
+    }
+
+    let content_length = match headers.get("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| HttpError::new(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpError::new(413, "Upload is too large"));
+    }
+
+    let request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    Ok((
+        request,
+        PendingBody {
+            reader,
+            content_length,
+        },
+    ))
+}
+
+fn read_line<R: BufRead>(reader: &mut R, header_bytes: &mut usize) -> Result<String, HttpError> {
+    let mut line = Vec::new();
+    let read = reader
+        .by_ref()
+        .take((MAX_HEADER_BYTES - *header_bytes) as u64)
+        .read_until(b'\n', &mut line)?;
+    *header_bytes += read;
+    if !line.ends_with(b"\n") {
+        return Err(HttpError::new(431, "Request headers are too large"));
+    }
+    let line = String::from_utf8(line).map_err(|_| HttpError::new(400, "Invalid header"))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_request_with_body() {
+        let raw = b"POST /v1/audio/transcriptions?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\nContent-Length: 5\r\n\r\nhello";
+        let (request, body) = read_head(&raw[..]).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/audio/transcriptions");
+        assert_eq!(request.header("authorization"), Some("Bearer abc"));
+        assert!(request.body.is_empty());
+        assert_eq!(body.read().unwrap(), b"hello");
+    }
+
+    #[test]
+    fn rejects_chunked_and_oversized_bodies() {
+        let chunked = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert_eq!(read_head(&chunked[..]).err().unwrap().status, 411);
+
+        let huge = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(read_head(huge.as_bytes()).err().unwrap().status, 413);
+    }
+
+    #[test]
+    fn rejects_body_shorter_than_content_length() {
+        let raw = b"POST / HTTP/1.1\r\nContent-Length: 1000000\r\n\r\nhello";
+        let (_, body) = read_head(&raw[..]).unwrap();
+        assert_eq!(body.read().unwrap_err().status, 400);
+    }
+
+    #[test]
+    fn writes_response() {
+        let mut out = Vec::new();
+        Response::new(200, "text/plain", "hi")
+            .write_to(&mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 2\r\nConnection: close\r\n\r\nhi"
+        );
+    }
+}
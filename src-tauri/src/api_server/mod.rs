@@ -0,0 +1,510 @@
+//! Opt-in local HTTP server exposing transcription as an OpenAI-compatible
+//! `POST /v1/audio/transcriptions` endpoint.
+//!
+//! The server only listens on 127.0.0.1. It is started, stopped and moved to
+//! another port by [`ApiServer::apply_settings`] whenever the API server
+//! settings change, and reports its state as `model-state-changed` events.
+
+mod http;
+mod multipart;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::decode_audio_bytes;
+use crate::managers::history::export::{write_segment_subtitles, HistoryExportFormat};
+use crate::managers::history::{TranscriptionOutput, TranscriptionSegment};
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::{ModelStateEvent, TranscriptionManager};
+use crate::settings::get_settings;
+use http::{Request, Response};
+use log::{debug, error, info, warn};
+use serde_json::json;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const TRANSCRIPTIONS_PATH: &str = "/v1/audio/transcriptions";
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+/// Connections served at once; each may buffer a body of up to
+/// [`http::MAX_BODY_BYTES`]
+const MAX_CONNECTIONS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ResponseFormat {
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+impl ResponseFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(Self::Json),
+            "text" => Some(Self::Text),
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "verbose_json" => Some(Self::VerboseJson),
+            _ => None,
+        }
+    }
+}
+
+/// Managers the request handlers need, shared by every connection thread
+struct Handler {
+    app_handle: AppHandle,
+    transcription_manager: Arc<TranscriptionManager>,
+    model_manager: Arc<ModelManager>,
+    /// Requests are transcribed one at a time
+    transcribe_lock: Mutex<()>,
+    /// Connections currently being served
+    connections: AtomicUsize,
+}
+
+struct RunningServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    accept_thread: thread::JoinHandle<()>,
+}
+
+pub struct ApiServer {
+    handler: Arc<Handler>,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl ApiServer {
+    pub fn new(
+        app_handle: &AppHandle,
+        transcription_manager: Arc<TranscriptionManager>,
+        model_manager: Arc<ModelManager>,
+    ) -> Self {
+        Self {
+            handler: Arc::new(Handler {
+                app_handle: app_handle.clone(),
+                transcription_manager,
+                model_manager,
+                transcribe_lock: Mutex::new(()),
+                connections: AtomicUsize::new(0),
+            }),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Starts, stops or restarts the server to match the current settings.
+    /// The token is read on every request, so changing it needs no restart.
+    pub fn apply_settings(&self) -> Result<(), String> {
+        let settings = get_settings(&self.handler.app_handle);
+        let mut running = self.running.lock().unwrap();
+
+        let wanted_port = settings
+            .api_server_enabled
+            .then_some(settings.api_server_port);
+        if running.as_ref().map(|server| server.port) == wanted_port {
+            return Ok(());
+        }
+
+        if let Some(server) = running.take() {
+            server.stop.store(true, Ordering::Relaxed);
+            let _ = server.accept_thread.join();
+            info!("API server on port {} stopped", server.port);
+            self.emit_state("api_server_stopped", None);
+        }
+
+        let Some(port) = wanted_port else {
+            return Ok(());
+        };
+        match self.start(port) {
+            Ok(server) => {
+                info!("API server listening on http://127.0.0.1:{}", port);
+                *running = Some(server);
+                self.emit_state("api_server_started", None);
+                Ok(())
+            }
+            Err(e) => {
+                let message = format!("Failed to start API server on port {}: {}", port, e);
+                error!("{}", message);
+                self.emit_state("api_server_failed", Some(message.clone()));
+                Err(message)
+            }
+        }
+    }
+
+    fn start(&self, port: u16) -> std::io::Result<RunningServer> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        // Non-blocking so the accept loop can notice the stop flag
+        listener.set_nonblocking(true)?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let handler = self.handler.clone();
+        let accept_thread = thread::spawn(move || {
+            while !stop_flag.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        if handler.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                            handler.connections.fetch_sub(1, Ordering::SeqCst);
+                            let _ = stream.set_nonblocking(false);
+                            let _ = error_response(503, "server_error", "Too many connections")
+                                .write_to(&mut stream);
+                            continue;
+                        }
+                        let handler = handler.clone();
+                        thread::spawn(move || {
+                            handler.serve(stream);
+                            handler.connections.fetch_sub(1, Ordering::SeqCst);
+                        });
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        thread::sleep(ACCEPT_POLL_INTERVAL);
+                    }
+                    Err(e) => {
+                        warn!("API server failed to accept a connection: {}", e);
+                        thread::sleep(ACCEPT_POLL_INTERVAL);
+                    }
+                }
+            }
+        });
+
+        Ok(RunningServer {
+            port,
+            stop,
+            accept_thread,
+        })
+    }
+
+    fn emit_state(&self, event_type: &str, error: Option<String>) {
+        let _ = self.handler.app_handle.emit(
+            "model-state-changed",
+            ModelStateEvent {
+                event_type: event_type.to_string(),
+                model_id: None,
+                model_name: None,
+                error,
+            },
+        );
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        if let Some(server) = self.running.get_mut().unwrap().take() {
+            server.stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Handler {
+    fn serve(&self, mut stream: TcpStream) {
+        let _ = stream.set_nonblocking(false);
+        let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+
+        let response = match http::read_head(&stream) {
+            Ok((mut request, body)) => match self.check(&request) {
+                Err(response) => response,
+                Ok(()) => match body.read() {
+                    Ok(body) => {
+                        request.body = body;
+                        self.transcribe(&request)
+                            .unwrap_or_else(|(status, message)| {
+                                error_response(status, "invalid_request_error", &message)
+                            })
+                    }
+                    Err(e) => error_response(e.status, "invalid_request_error", &e.message),
+                },
+            },
+            Err(e) => error_response(e.status, "invalid_request_error", &e.message),
+        };
+        if let Err(e) = response.write_to(&mut stream) {
+            debug!("API server failed to write a response: {}", e);
+        }
+    }
+
+    /// Decides from the headers alone whether to accept the request, before
+    /// its body is read
+    fn check(&self, request: &Request) -> Result<(), Response> {
+        if let Err(message) = check_local_origin(request) {
+            return Err(error_response(403, "invalid_request_error", message));
+        }
+        if request.path != TRANSCRIPTIONS_PATH {
+            return Err(error_response(404, "invalid_request_error", "Not found"));
+        }
+        if request.method != "POST" {
+            return Err(error_response(405, "invalid_request_error", "Use POST"));
+        }
+
+        let settings = get_settings(&self.app_handle);
+        if let Some(token) = settings.api_server_token.filter(|t| !t.is_empty()) {
+            let authorized = request
+                .header("authorization")
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .is_some_and(|given| tokens_match(given.trim(), &token));
+            if !authorized {
+                return Err(error_response(
+                    401,
+                    "authentication_error",
+                    "Invalid API token",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn transcribe(&self, request: &Request) -> Result<Response, (u16, String)> {
+        let boundary = request
+            .header("content-type")
+            .and_then(multipart::boundary_from_content_type)
+            .ok_or((400, "Expected a multipart/form-data body".to_string()))?;
+        let parts = multipart::parse(&request.body, boundary).map_err(|e| (400, e))?;
+        let field = |name: &str| {
+            parts
+                .iter()
+                .find(|part| part.name == name)
+                .and_then(|part| part.text())
+                .filter(|text| !text.is_empty())
+        };
+
+        let file = parts
+            .iter()
+            .find(|part| part.name == "file")
+            .ok_or((400, "Missing 'file' field".to_string()))?;
+        let format = match field("response_format") {
+            Some(value) => ResponseFormat::parse(value)
+                .ok_or((400, format!("Unsupported response_format: {}", value)))?,
+            None => ResponseFormat::Json,
+        };
+        let model_id = self.resolve_model(field("model"))?;
+        let language = field("language").map(str::to_string);
+
+        let extension = file
+            .filename
+            .as_deref()
+            .and_then(|name| Path::new(name).extension())
+            .and_then(|ext| ext.to_str());
+        let samples = decode_audio_bytes(file.data.to_vec(), extension)
+            .map_err(|e| (400, format!("Failed to decode audio: {}", e)))?;
+        let duration_ms = (samples.len() as u64 * 1000) / WHISPER_SAMPLE_RATE as u64;
+
+        let tm = &self.transcription_manager;
+        let _guard = self.transcribe_lock.lock().unwrap();
+
+        // A model picked by the request is only loaded for this request, so
+        // dictation keeps using the model selected in the app
+        let result = tm.with_model(&model_id, || {
+            tm.transcribe_long_form(&samples, language.as_deref(), |_| ControlFlow::Continue(()))
+        });
+
+        let output = result.map_err(|e| (500, format!("Transcription failed: {}", e)))?;
+        debug!(
+            "API server transcribed {} ms of audio with {}",
+            duration_ms, model_id
+        );
+        let language = language.unwrap_or_else(|| output.metadata.language.unwrap_or_default());
+        render(&output, format, duration_ms, &language).map_err(|e| (500, e))
+    }
+
+    /// Uses the requested model if it is a downloaded Handy model. Other names
+    /// (such as OpenAI's `whisper-1`) fall back to the loaded or selected model.
+    fn resolve_model(&self, requested: Option<&str>) -> Result<String, (u16, String)> {
+        if let Some(id) = requested {
+            if let Some(info) = self.model_manager.get_model_info(id) {
+                if !info.is_downloaded {
+                    return Err((400, format!("Model {} is not downloaded", id)));
+                }
+                return Ok(info.id);
+            }
+        }
+
+        let model_id = self
+            .transcription_manager
+            .get_current_model()
+            .unwrap_or_else(|| get_settings(&self.app_handle).selected_model);
+        if model_id.is_empty() {
+            return Err((503, "No model selected".to_string()));
+        }
+        Ok(model_id)
+    }
+}
+
+fn render(
+    output: &TranscriptionOutput,
+    format: ResponseFormat,
+    duration_ms: u64,
+    language: &str,
+) -> Result<Response, String> {
+    let segments = segments_or_whole(output, duration_ms);
+    let response = match format {
+        ResponseFormat::Json => Response::json(200, &json!({ "text": output.text })),
+        ResponseFormat::Text => {
+            Response::new(200, "text/plain; charset=utf-8", output.text.clone())
+        }
+        ResponseFormat::Srt | ResponseFormat::Vtt => {
+            let (export_format, content_type) = if format == ResponseFormat::Srt {
+                (HistoryExportFormat::Srt, "application/x-subrip")
+            } else {
+                (HistoryExportFormat::Vtt, "text/vtt")
+            };
+            let mut body = Vec::new();
+            write_segment_subtitles(&mut body, &segments, export_format)
+                .map_err(|e| e.to_string())?;
+            Response::new(200, content_type, body)
+        }
+        ResponseFormat::VerboseJson => {
+            let mut body = json!({
+                "task": "transcribe",
+                "language": language,
+                "duration": duration_ms as f64 / 1000.0,
+                "text": output.text,
+                "segments": segments
+                    .iter()
+                    .enumerate()
+                    .map(|(id, s)| json!({
+                        "id": id,
+                        "start": s.start_ms as f64 / 1000.0,
+                        "end": s.end_ms as f64 / 1000.0,
+                        "text": s.text,
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+            if let Some(words) = &output.words {
+                body["words"] = words
+                    .iter()
+                    .map(|w| {
+                        json!({
+                            "word": w.text.trim(),
+                            "start": w.start_ms as f64 / 1000.0,
+                            "end": w.end_ms as f64 / 1000.0,
+                        })
+                    })
+                    .collect();
+            }
+            Response::json(200, &body)
+        }
+    };
+    Ok(response)
+}
+
+/// Segments of the output, or one segment spanning the whole recording for
+/// engines that report no timestamps
+fn segments_or_whole(output: &TranscriptionOutput, duration_ms: u64) -> Vec<TranscriptionSegment> {
+    if !output.segments.is_empty() || output.text.trim().is_empty() {
+        return output.segments.clone();
+    }
+    vec![TranscriptionSegment {
+        start_ms: 0,
+        end_ms: duration_ms,
+        text: output.text.clone(),
+    }]
+}
+
+/// Rejects requests sent by web pages: browsers add an `Origin` header to
+/// cross-site POSTs, and a `Host` other than the loopback address means the
+/// page reached the server through DNS rebinding
+fn check_local_origin(request: &Request) -> Result<(), &'static str> {
+    if request.header("origin").is_some() {
+        return Err("Requests from web pages are not allowed");
+    }
+    if let Some(host) = request.header("host") {
+        let host = host.rsplit_once(':').map_or(host, |(name, _)| name);
+        if host != "127.0.0.1" && !host.eq_ignore_ascii_case("localhost") {
+            return Err("Requests must be sent to 127.0.0.1 or localhost");
+        }
+    }
+    Ok(())
+}
+
+/// Compares every byte even after a mismatch, so the response time doesn't
+/// reveal how much of a guessed token was right
+fn tokens_match(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Error body in the shape OpenAI clients expect
+fn error_response(status: u16, error_type: &str, message: &str) -> Response {
+    Response::json(
+        status,
+        &json!({ "error": { "message": message, "type": error_type } }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output() -> TranscriptionOutput {
+        TranscriptionOutput {
+            text: "Hello world.".to_string(),
+            segments: Vec::new(),
+            words: None,
+            ..Default::default()
+        }
+    }
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: TRANSCRIPTIONS_PATH.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rejects_requests_from_web_pages() {
+        assert!(check_local_origin(&request(&[("host", "127.0.0.1:5800")])).is_ok());
+        assert!(check_local_origin(&request(&[("host", "localhost")])).is_ok());
+        assert!(check_local_origin(&request(&[])).is_ok());
+        assert!(check_local_origin(&request(&[
+            ("host", "127.0.0.1:5800"),
+            ("origin", "https://example.com"),
+        ]))
+        .is_err());
+        assert!(check_local_origin(&request(&[("host", "attacker.example:5800")])).is_err());
+    }
+
+    #[test]
+    fn tokens_match_only_when_equal() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secret1", "secret"));
+        assert!(!tokens_match("", "secret"));
+    }
+
+    #[test]
+    fn verbose_json_covers_whole_recording_without_timestamps() {
+        let response = render(&output(), ResponseFormat::VerboseJson, 2500, "en").unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(body["duration"], 2.5);
+        assert_eq!(body["segments"][0]["end"], 2.5);
+        assert_eq!(body["segments"][0]["text"], "Hello world.");
+        assert!(body.get("words").is_none());
+    }
+
+    #[test]
+    fn renders_srt_from_segments() {
+        let mut output = output();
+        output.segments = vec![TranscriptionSegment {
+            start_ms: 1000,
+            end_ms: 2000,
+            text: "Hello world.".to_string(),
+        }];
+        let response = render(&output, ResponseFormat::Srt, 3000, "en").unwrap();
+        assert_eq!(response.content_type, "application/x-subrip");
+        let body = String::from_utf8(response.body).unwrap();
+        assert!(body.contains("1\n00:00:01,000 --> 00:00:02,000\nHello world.\n"));
+    }
+}
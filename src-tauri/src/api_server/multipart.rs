@@ -0,0 +1,142 @@
+//! Parser for `multipart/form-data` request bodies.
+
+/// One field of a form. `filename` is set for file uploads.
+#[derive(Debug)]
+pub struct Part<'a> {
+    pub name: String,
+    pub filename: Option<String>,
+    pub data: &'a [u8],
+}
+
+impl Part<'_> {
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(self.data).ok().map(str::trim)
+    }
+}
+
+/// Reads the boundary from a `multipart/form-data` Content-Type header
+pub fn boundary_from_content_type(content_type: &str) -> Option<&str> {
+    let mut params = content_type.split(';');
+    let mime = params.next()?.trim();
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params.find_map(|param| {
+        let (key, value) = param.trim().split_once('=')?;
+        key.eq_ignore_ascii_case("boundary")
+            .then(|| value.trim_matches('"'))
+            .filter(|b| !b.is_empty())
+    })
+}
+
+pub fn parse<'a>(body: &'a [u8], boundary: &str) -> Result<Vec<Part<'a>>, String> {
+    let delimiter = format!("--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+
+    let start = find(body, delimiter).ok_or("Missing multipart boundary")?;
+    let mut rest = &body[start + delimiter.len()..];
+    let mut parts = Vec::new();
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        rest = rest
+            .strip_prefix(b"\r\n")
+            .ok_or("Malformed multipart boundary")?;
+
+        let header_end = find(rest, b"\r\n\r\n").ok_or("Malformed multipart headers")?;
+        let headers =
+            std::str::from_utf8(&rest[..header_end]).map_err(|_| "Invalid multipart headers")?;
+        rest = &rest[header_end + 4..];
+
+        // The part's data runs up to the CRLF before the next delimiter
+        let end = find_delimiter(rest, delimiter).ok_or("Unterminated multipart body")?;
+        let data = &rest[..end];
+        rest = &rest[end + 2 + delimiter.len()..];
+
+        let disposition = headers
+            .split("\r\n")
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.trim()
+                    .eq_ignore_ascii_case("content-disposition")
+                    .then_some(value)
+            })
+            .ok_or("Multipart part without Content-Disposition")?;
+        let name = disposition_param(disposition, "name").ok_or("Multipart part without name")?;
+
+        parts.push(Part {
+            name,
+            filename: disposition_param(disposition, "filename"),
+            data,
+        });
+    }
+}
+
+fn disposition_param(disposition: &str, key: &str) -> Option<String> {
+    disposition.split(';').skip(1).find_map(|param| {
+        let (k, v) = param.trim().split_once('=')?;
+        k.trim()
+            .eq_ignore_ascii_case(key)
+            .then(|| v.trim().trim_matches('"').to_string())
+    })
+}
+
+fn find_delimiter(haystack: &[u8], delimiter: &[u8]) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(pos) = find(&haystack[offset..], b"\r\n") {
+        let at = offset + pos;
+        if haystack[at + 2..].starts_with(delimiter) {
+            return Some(at);
+        }
+        offset = at + 2;
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_boundary() {
+        assert_eq!(
+            boundary_from_content_type("multipart/form-data; boundary=\"abc123\""),
+            Some("abc123")
+        );
+        assert_eq!(boundary_from_content_type("application/json"), None);
+    }
+
+    #[test]
+    fn parses_fields_and_files() {
+        let body = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"model\"\r\n\r\n\
+whisper-1\r\n\
+--XyZ\r\n\
+Content-Disposition: form-data; name=\"file\"; filename=\"clip.wav\"\r\n\
+Content-Type: audio/wav\r\n\r\n\
+RIFF\r\n--not-a-boundary\r\n\
+--XyZ--\r\n";
+
+        let parts = parse(body, "XyZ").unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "model");
+        assert_eq!(parts[0].text(), Some("whisper-1"));
+        assert_eq!(parts[1].name, "file");
+        assert_eq!(parts[1].filename.as_deref(), Some("clip.wav"));
+        assert_eq!(parts[1].data, b"RIFF\r\n--not-a-boundary");
+    }
+
+    #[test]
+    fn rejects_unterminated_body() {
+        let body = b"--XyZ\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\ndata";
+        assert!(parse(body, "XyZ").is_err());
+    }
+}
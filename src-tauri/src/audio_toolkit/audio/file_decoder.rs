@@ -1,16 +1,27 @@
 use anyhow::{Context, Result};
 use log::{debug, info};
 use rubato::{FftFixedIn, Resampler};
+use std::io::Cursor;
 use std::path::Path;
 use symphonia::core::audio::SampleBuffer;
 use symphonia::core::codecs::DecoderOptions;
 use symphonia::core::formats::FormatOptions;
-use symphonia::core::io::MediaSourceStream;
+use symphonia::core::io::{MediaSource, MediaSourceStream};
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
 const TARGET_SAMPLE_RATE: usize = 16_000;
 
+/// File extensions `decode_audio_file` can read
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "m4a", "aac", "ogg", "oga"];
+
+/// Whether `path` has one of the `SUPPORTED_EXTENSIONS` (case-insensitive)
+pub fn is_supported_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
 /// Decode an audio file to mono f32 samples at 16kHz.
 ///
 /// Supports WAV, MP3, FLAC, M4A/AAC, and OGG/Vorbis via symphonia.
@@ -18,11 +29,21 @@ pub fn decode_audio_file(path: &Path) -> Result<Vec<f32>> {
     let file = std::fs::File::open(path)
         .with_context(|| format!("Failed to open audio file: {}", path.display()))?;
 
-    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    decode_media_source(Box::new(file), path.extension().and_then(|e| e.to_str()))
+}
+
+/// Decode audio held in memory, such as an upload, to mono f32 samples at
+/// 16kHz. `extension` is used as a hint for the container format.
+pub fn decode_audio_bytes(bytes: Vec<u8>, extension: Option<&str>) -> Result<Vec<f32>> {
+    decode_media_source(Box::new(Cursor::new(bytes)), extension)
+}
+
+fn decode_media_source(source: Box<dyn MediaSource>, extension: Option<&str>) -> Result<Vec<f32>> {
+    let mss = MediaSourceStream::new(source, Default::default());
 
     // Provide a hint based on file extension
     let mut hint = Hint::new();
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+    if let Some(ext) = extension {
         hint.with_extension(ext);
     }
 
@@ -7,7 +7,9 @@ mod utils;
 mod visualizer;
 
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use file_decoder::decode_audio_file;
+pub use file_decoder::{
+    decode_audio_bytes, decode_audio_file, is_supported_audio_file, SUPPORTED_EXTENSIONS,
+};
 pub use recorder::AudioRecorder;
 pub use resampler::FrameResampler;
 pub use utils::save_wav_file;
@@ -16,6 +16,17 @@ use crate::audio_toolkit::{
     VoiceActivityDetector,
 };
 
+type SamplesCallback = Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>;
+
+/// Segments shorter than this keep growing across pauses (0.5 s at 16 kHz)
+const MIN_SEGMENT_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize / 2;
+/// Continuous speech is cut into segments of at most this length (10 s at 16 kHz)
+const MAX_SEGMENT_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 10;
+/// How far before the limit to look for a quiet place to cut (2 s at 16 kHz)
+const CUT_SEARCH_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 2;
+/// Length of the frames compared when looking for that place (30 ms at 16 kHz)
+const CUT_FRAME_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 3 / 100;
+
 enum Cmd {
     Start,
     Stop(mpsc::Sender<Vec<f32>>),
@@ -27,7 +38,8 @@ pub struct AudioRecorder {
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SamplesCallback>,
+    segment_cb: Option<SamplesCallback>,
 }
 
 impl AudioRecorder {
@@ -38,6 +50,7 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            segment_cb: None,
         })
     }
 
@@ -54,6 +67,19 @@ impl AudioRecorder {
         self
     }
 
+    /// Receives each speech segment of the current recording as soon as it ends.
+    ///
+    /// Segments are split at VAD pauses (or, in continuous speech, at the
+    /// quietest moment before a length limit), and whatever remains is
+    /// delivered on stop, before the full recording is returned.
+    pub fn with_segment_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(Vec<f32>) + Send + Sync + 'static,
+    {
+        self.segment_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -74,6 +100,7 @@ impl AudioRecorder {
         let vad = self.vad.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let segment_cb = self.segment_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -117,7 +144,7 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb, segment_cb);
             // stream is dropped here, after run_consumer returns
         });
 
@@ -239,12 +266,73 @@ impl AudioRecorder {
     }
 }
 
+/// Splits the recorded speech into segments at VAD pauses
+#[derive(Default)]
+struct SpeechSegmenter {
+    /// Start of the pending segment in the recorded samples
+    start: usize,
+    in_speech: bool,
+}
+
+impl SpeechSegmenter {
+    fn reset(&mut self) {
+        self.start = 0;
+        self.in_speech = false;
+    }
+
+    /// Called after each frame; returns a finished segment, if any
+    fn on_frame(&mut self, is_speech: bool, samples: &[f32]) -> Option<Vec<f32>> {
+        let pending = samples.len() - self.start;
+        let paused = self.in_speech && !is_speech && pending >= MIN_SEGMENT_SAMPLES;
+        self.in_speech = is_speech;
+        if paused {
+            self.flush(samples)
+        } else if pending >= MAX_SEGMENT_SAMPLES {
+            self.cut(samples)
+        } else {
+            None
+        }
+    }
+
+    /// Cuts continuous speech in the middle of its quietest frame near the
+    /// limit, which is most likely a gap between words. The rest stays pending.
+    fn cut(&mut self, samples: &[f32]) -> Option<Vec<f32>> {
+        let search_start =
+            (samples.len() - CUT_SEARCH_SAMPLES).max(self.start + MIN_SEGMENT_SAMPLES);
+        let energy = |frame: &[f32]| frame.iter().map(|s| s * s).sum::<f32>();
+        // Searching backwards keeps the latest of equally quiet frames
+        let cut = samples[search_start..]
+            .chunks_exact(CUT_FRAME_SAMPLES)
+            .enumerate()
+            .rev()
+            .min_by(|(_, a), (_, b)| energy(a).total_cmp(&energy(b)))
+            .map_or(samples.len(), |(index, _)| {
+                search_start + index * CUT_FRAME_SAMPLES + CUT_FRAME_SAMPLES / 2
+            });
+
+        let segment = samples[self.start..cut].to_vec();
+        self.start = cut;
+        Some(segment)
+    }
+
+    /// Takes everything recorded since the last segment
+    fn flush(&mut self, samples: &[f32]) -> Option<Vec<f32>> {
+        if samples.len() <= self.start {
+            return None;
+        }
+        let segment = samples[self.start..].to_vec();
+        self.start = samples.len();
+        Some(segment)
+    }
+}
+
 fn run_consumer(
     in_sample_rate: u32,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SamplesCallback>,
+    segment_cb: Option<SamplesCallback>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -254,6 +342,7 @@ fn run_consumer(
 
     let mut processed_samples = Vec::<f32>::new();
     let mut recording = false;
+    let mut segmenter = SpeechSegmenter::default();
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -266,24 +355,29 @@ fn run_consumer(
         4000.0, // vocal_max_hz
     );
 
+    /// Returns whether the frame was kept as speech
     fn handle_frame(
         samples: &[f32],
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(buf);
+                    true
+                }
+                VadFrame::Noise => false,
             }
         } else {
             out_buf.extend_from_slice(samples);
+            true
         }
     }
 
@@ -302,7 +396,12 @@ fn run_consumer(
 
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+            let is_speech = handle_frame(frame, recording, &vad, &mut processed_samples);
+            if let Some(cb) = segment_cb.as_ref().filter(|_| recording) {
+                if let Some(segment) = segmenter.on_frame(is_speech, &processed_samples) {
+                    cb(segment);
+                }
+            }
         });
 
         // non-blocking check for a command
@@ -310,6 +409,7 @@ fn run_consumer(
             match cmd {
                 Cmd::Start => {
                     processed_samples.clear();
+                    segmenter.reset();
                     recording = true;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
@@ -322,14 +422,21 @@ fn run_consumer(
                     // Drain any audio chunks that were captured but not yet consumed
                     while let Ok(remaining) = sample_rx.try_recv() {
                         frame_resampler.push(&remaining, &mut |frame: &[f32]| {
-                            handle_frame(frame, true, &vad, &mut processed_samples)
+                            handle_frame(frame, true, &vad, &mut processed_samples);
                         });
                     }
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        handle_frame(frame, true, &vad, &mut processed_samples);
                     });
 
+                    // Deliver the last segment before the full recording
+                    if let Some(cb) = &segment_cb {
+                        if let Some(segment) = segmenter.flush(&processed_samples) {
+                            cb(segment);
+                        }
+                    }
+
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
                 }
                 Cmd::Shutdown => return,
@@ -337,3 +444,64 @@ fn run_consumer(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segmenter_splits_at_pauses() {
+        let mut segmenter = SpeechSegmenter::default();
+        let mut samples = vec![0.0; MIN_SEGMENT_SAMPLES];
+
+        assert_eq!(segmenter.on_frame(true, &samples), None);
+        let segment = segmenter.on_frame(false, &samples).unwrap();
+        assert_eq!(segment.len(), MIN_SEGMENT_SAMPLES);
+
+        // Silence after the flush produces nothing new
+        assert_eq!(segmenter.on_frame(false, &samples), None);
+
+        samples.extend(vec![1.0; 480]);
+        assert_eq!(segmenter.on_frame(true, &samples), None);
+        assert_eq!(segmenter.flush(&samples), Some(vec![1.0; 480]));
+        assert_eq!(segmenter.flush(&samples), None);
+    }
+
+    #[test]
+    fn segmenter_merges_short_segments() {
+        let mut segmenter = SpeechSegmenter::default();
+        let samples = vec![0.0; 480];
+
+        assert_eq!(segmenter.on_frame(true, &samples), None);
+        assert_eq!(segmenter.on_frame(false, &samples), None);
+        assert_eq!(segmenter.flush(&samples).map(|s| s.len()), Some(480));
+    }
+
+    #[test]
+    fn segmenter_cuts_long_speech_at_the_quietest_frame() {
+        let mut segmenter = SpeechSegmenter::default();
+        let mut samples = vec![0.5; MAX_SEGMENT_SAMPLES];
+        let quiet = MAX_SEGMENT_SAMPLES - CUT_SEARCH_SAMPLES + 20 * CUT_FRAME_SAMPLES;
+        samples[quiet..quiet + CUT_FRAME_SAMPLES].fill(0.01);
+
+        let segment = segmenter.on_frame(true, &samples).unwrap();
+        let cut = quiet + CUT_FRAME_SAMPLES / 2;
+        assert_eq!(segment.len(), cut);
+        assert!(segmenter.in_speech);
+
+        // The speech after the cut starts the next segment
+        assert_eq!(
+            segmenter.flush(&samples).map(|s| s.len()),
+            Some(MAX_SEGMENT_SAMPLES - cut)
+        );
+    }
+
+    #[test]
+    fn segmenter_cuts_uniform_speech_near_the_limit() {
+        let mut segmenter = SpeechSegmenter::default();
+        let samples = vec![0.5; MAX_SEGMENT_SAMPLES];
+
+        let segment = segmenter.on_frame(true, &samples).unwrap();
+        assert!(segment.len() > MAX_SEGMENT_SAMPLES - CUT_FRAME_SAMPLES * 2);
+    }
+}
@@ -5,9 +5,13 @@ pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    decode_audio_file, list_input_devices, list_output_devices, save_wav_file, AudioRecorder,
-    CpalDeviceInfo,
+    decode_audio_bytes, decode_audio_file, is_supported_audio_file, list_input_devices,
+    list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo, SUPPORTED_EXTENSIONS,
+};
+pub use text::{
+    apply_custom_words, apply_inverse_text_normalization, apply_replacement_rules,
+    build_replacement_regex, diff_word_substitutions, filler_words_for_language,
+    filter_transcription_output, format_spoken_commands,
 };
-pub use text::{apply_custom_words, filter_transcription_output};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
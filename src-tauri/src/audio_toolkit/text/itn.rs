@@ -0,0 +1,867 @@
+//! Rule-based inverse text normalization (ITN).
+//!
+//! Rewrites spoken-form numbers into their written form, e.g.
+//! "twenty three dollars and fifty cents on march third" becomes
+//! "$23.50 on March 3". Covers cardinals, ordinals, decimals, percentages,
+//! currency, dates, times, years and digit sequences such as phone numbers.
+//!
+//! Only English is supported for now; text in other languages is returned
+//! unchanged. Standalone numbers below ten ("one", "first") are left spelled
+//! out, since they are far more often words than quantities.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberWord {
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Scale(u64),
+}
+
+const UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: &[&str] = &[
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: &[&str] = &[
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: &[(&str, u64)] = &[
+    ("hundred", 100),
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+const ORDINAL_UNITS: &[&str] = &[
+    "zeroth", "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+];
+const ORDINAL_TEENS: &[&str] = &[
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+const ORDINAL_TENS: &[&str] = &[
+    "twentieth",
+    "thirtieth",
+    "fortieth",
+    "fiftieth",
+    "sixtieth",
+    "seventieth",
+    "eightieth",
+    "ninetieth",
+];
+const ORDINAL_SCALES: &[(&str, u64)] = &[
+    ("hundredth", 100),
+    ("thousandth", 1_000),
+    ("millionth", 1_000_000),
+    ("billionth", 1_000_000_000),
+];
+
+const MONTHS: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Digit sequences at least this long are written out as digits
+const MIN_DIGIT_SEQUENCE: usize = 7;
+
+/// A whitespace-separated word with the punctuation around it split off
+#[derive(Debug, Clone)]
+struct Token {
+    leading: String,
+    core: String,
+    trailing: String,
+    /// Lowercased alphanumeric characters of `core`, used for matching
+    norm: String,
+}
+
+impl Token {
+    fn new(leading: &str, core: &str, trailing: &str) -> Self {
+        Token {
+            leading: leading.to_string(),
+            core: core.to_string(),
+            trailing: trailing.to_string(),
+            norm: core
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect(),
+        }
+    }
+
+    fn original(&self) -> String {
+        format!("{}{}{}", self.leading, self.core, self.trailing)
+    }
+
+    /// Whether a number span may continue past this token
+    fn joins_next(&self) -> bool {
+        self.trailing.is_empty()
+    }
+}
+
+fn classify(word: &str) -> Option<(NumberWord, bool)> {
+    let find = |table: &[&str]| table.iter().position(|w| *w == word).map(|i| i as u64);
+    let scale = |table: &[(&str, u64)]| table.iter().find(|(w, _)| *w == word).map(|(_, v)| *v);
+
+    if let Some(v) = find(UNITS) {
+        Some((NumberWord::Unit(v), false))
+    } else if let Some(v) = find(TEENS) {
+        Some((NumberWord::Teen(10 + v), false))
+    } else if let Some(v) = find(TENS) {
+        Some((NumberWord::Tens(20 + 10 * v), false))
+    } else if let Some(v) = scale(SCALES) {
+        Some((NumberWord::Scale(v), false))
+    } else if let Some(v) = find(ORDINAL_UNITS) {
+        Some((NumberWord::Unit(v), true))
+    } else if let Some(v) = find(ORDINAL_TEENS) {
+        Some((NumberWord::Teen(10 + v), true))
+    } else if let Some(v) = find(ORDINAL_TENS) {
+        Some((NumberWord::Tens(20 + 10 * v), true))
+    } else {
+        scale(ORDINAL_SCALES).map(|v| (NumberWord::Scale(v), true))
+    }
+}
+
+/// Value of a single spoken digit, accepting "oh" for zero
+fn digit_value(word: &str) -> Option<u64> {
+    if word == "oh" {
+        return Some(0);
+    }
+    match classify(word) {
+        Some((NumberWord::Unit(v), false)) => Some(v),
+        _ => None,
+    }
+}
+
+fn month_index(word: &str) -> Option<usize> {
+    MONTHS.iter().position(|m| m.eq_ignore_ascii_case(word))
+}
+
+/// Whether a hyphenated word is a compound number such as "twenty-three" or "thirty-first"
+fn is_compound_number(parts: &[&str]) -> bool {
+    parts.len() == 2
+        && matches!(
+            classify(&parts[0].to_lowercase()),
+            Some((NumberWord::Tens(_), false))
+        )
+        && matches!(
+            classify(&parts[1].to_lowercase()),
+            Some((NumberWord::Unit(1..=9), _))
+        )
+}
+
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    for raw in line.split_whitespace() {
+        let Some(start) = raw.find(|c: char| c.is_alphanumeric()) else {
+            tokens.push(Token::new("", raw, ""));
+            continue;
+        };
+        let end = raw
+            .char_indices()
+            .filter(|(_, c)| c.is_alphanumeric())
+            .map(|(i, c)| i + c.len_utf8())
+            .next_back()
+            .unwrap_or(raw.len());
+        let (leading, core, trailing) = (&raw[..start], &raw[start..end], &raw[end..]);
+
+        let parts: Vec<&str> = core.split('-').collect();
+        if is_compound_number(&parts) {
+            tokens.push(Token::new(leading, parts[0], ""));
+            tokens.push(Token::new("", parts[1], trailing));
+        } else {
+            tokens.push(Token::new(leading, core, trailing));
+        }
+    }
+
+    tokens
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cardinal {
+    value: u64,
+    len: usize,
+    ordinal: bool,
+}
+
+/// Parses a spelled-out number from the start of `tokens`
+///
+/// Stops at the first word that can't extend the number, so "twenty three
+/// four" yields 23. An ordinal word ("twenty first") always ends the number.
+fn parse_cardinal(tokens: &[Token]) -> Option<Cardinal> {
+    let mut total = 0;
+    let mut current = 0;
+    let mut last: Option<NumberWord> = None;
+    let mut last_scale = u64::MAX;
+    let mut len = 0;
+    let mut ordinal = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if i > 0 && !tokens[i - 1].joins_next() {
+            break;
+        }
+        let norm = tokens[i].norm.as_str();
+
+        // "a hundred", "a thousand"
+        if i == 0 && norm == "a" {
+            let next_is_scale = tokens
+                .get(1)
+                .is_some_and(|t| matches!(classify(&t.norm), Some((NumberWord::Scale(_), _))));
+            if !next_is_scale || !tokens[0].joins_next() {
+                return None;
+            }
+            current = 1;
+            last = Some(NumberWord::Unit(1));
+            i += 1;
+            continue;
+        }
+
+        // "one hundred and five"
+        if norm == "and" && matches!(last, Some(NumberWord::Scale(_))) {
+            let continues = tokens.get(i + 1).is_some_and(|t| {
+                matches!(
+                    classify(&t.norm),
+                    Some((
+                        NumberWord::Unit(1..) | NumberWord::Teen(_) | NumberWord::Tens(_),
+                        _
+                    ))
+                )
+            });
+            if !continues {
+                break;
+            }
+            i += 1;
+            continue;
+        }
+
+        let Some((word, is_ordinal)) = classify(norm) else {
+            break;
+        };
+
+        let accepted = match word {
+            NumberWord::Unit(0) => last.is_none(),
+            NumberWord::Unit(v) => {
+                let ok = matches!(
+                    last,
+                    None | Some(NumberWord::Tens(_)) | Some(NumberWord::Scale(_))
+                );
+                if ok {
+                    current += v;
+                }
+                ok
+            }
+            NumberWord::Teen(v) | NumberWord::Tens(v) => {
+                let ok = matches!(last, None | Some(NumberWord::Scale(_)));
+                if ok {
+                    current += v;
+                }
+                ok
+            }
+            NumberWord::Scale(100) => {
+                let ok = matches!(last, Some(NumberWord::Unit(_)) | Some(NumberWord::Teen(_)))
+                    && (1..100).contains(&current);
+                if ok {
+                    current *= 100;
+                }
+                ok
+            }
+            NumberWord::Scale(scale) => {
+                let ok = last.is_some() && current > 0 && scale < last_scale;
+                if ok {
+                    total += current * scale;
+                    current = 0;
+                    last_scale = scale;
+                }
+                ok
+            }
+        };
+        if !accepted {
+            break;
+        }
+
+        last = Some(word);
+        i += 1;
+        len = i;
+
+        if is_ordinal {
+            ordinal = true;
+            break;
+        }
+        if word == NumberWord::Unit(0) {
+            break;
+        }
+    }
+
+    if len == 0 {
+        return None;
+    }
+    Some(Cardinal {
+        value: total + current,
+        len,
+        ordinal,
+    })
+}
+
+/// Parses spoken digits ("one four one five") into a digit string
+fn parse_digits(tokens: &[Token]) -> (String, usize) {
+    let mut digits = String::new();
+    for token in tokens {
+        let Some(d) = digit_value(&token.norm) else {
+            break;
+        };
+        digits.push_str(&d.to_string());
+        if !token.joins_next() {
+            break;
+        }
+    }
+    let len = digits.len();
+    (digits, len)
+}
+
+/// A number that may carry a fractional part
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Number {
+    integer: u64,
+    fraction: Option<String>,
+    ordinal: bool,
+    len: usize,
+    /// Whether the number was already written with digits
+    numeric: bool,
+}
+
+impl Number {
+    fn render(&self) -> String {
+        match &self.fraction {
+            Some(fraction) => format!("{}.{}", format_integer(self.integer), fraction),
+            None => format_integer(self.integer),
+        }
+    }
+}
+
+/// Parses a number from the start of `tokens`: a spelled-out cardinal or
+/// ordinal, an optional "point" and digits, or a number already in digits
+fn parse_number(tokens: &[Token]) -> Option<Number> {
+    let first = tokens.first()?;
+
+    if let Some((integer, fraction)) = parse_numeric(&first.core) {
+        return Some(Number {
+            integer,
+            fraction,
+            ordinal: false,
+            len: 1,
+            numeric: true,
+        });
+    }
+
+    let (integer, ordinal, len) = if first.norm == "point" {
+        (0, false, 0)
+    } else {
+        let cardinal = parse_cardinal(tokens)?;
+        (cardinal.value, cardinal.ordinal, cardinal.len)
+    };
+
+    let mut number = Number {
+        integer,
+        fraction: None,
+        ordinal,
+        len,
+        numeric: false,
+    };
+
+    let point = tokens.get(len);
+    let can_continue = len == 0 || tokens[len - 1].joins_next();
+    if !ordinal && can_continue && point.is_some_and(|t| t.norm == "point" && t.joins_next()) {
+        let (digits, count) = parse_digits(&tokens[len + 1..]);
+        if count > 0 {
+            number.fraction = Some(digits);
+            number.len = len + 1 + count;
+        }
+    }
+
+    (number.len > 0).then_some(number)
+}
+
+/// Parses "42" or "3.14" into integer and fractional digits
+fn parse_numeric(core: &str) -> Option<(u64, Option<String>)> {
+    let (integer, fraction) = match core.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (core, None),
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(integer) || !fraction.is_none_or(all_digits) {
+        return None;
+    }
+    Some((integer.parse().ok()?, fraction.map(str::to_string)))
+}
+
+/// Formats an integer, grouping thousands from 10,000 up
+fn format_integer(n: u64) -> String {
+    let digits = n.to_string();
+    if n < 10_000 {
+        return digits;
+    }
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn ordinal_suffix(n: u64) -> &'static str {
+    match (n % 100, n % 10) {
+        (11..=13, _) => "th",
+        (_, 1) => "st",
+        (_, 2) => "nd",
+        (_, 3) => "rd",
+        _ => "th",
+    }
+}
+
+/// Runs of seven or more spoken digits, formatted as phone numbers where the length fits
+fn parse_digit_sequence(tokens: &[Token]) -> Option<(String, usize)> {
+    if tokens.first()?.norm == "oh" {
+        return None;
+    }
+    let (digits, len) = parse_digits(tokens);
+    if len < MIN_DIGIT_SEQUENCE {
+        return None;
+    }
+    let text = match len {
+        7 => format!("{}-{}", &digits[..3], &digits[3..]),
+        10 => format!("{}-{}-{}", &digits[..3], &digits[3..6], &digits[6..]),
+        11 if digits.starts_with('1') => {
+            format!("+1 {}-{}-{}", &digits[1..4], &digits[4..7], &digits[7..])
+        }
+        _ => digits,
+    };
+    Some((text, len))
+}
+
+fn meridiem(token: &Token) -> Option<&'static str> {
+    match token.norm.as_str() {
+        "am" => Some("AM"),
+        "pm" => Some("PM"),
+        _ => None,
+    }
+}
+
+/// Two-digit groups as spoken in times and years: "oh five", "fifteen", "forty five"
+fn parse_two_digits(tokens: &[Token]) -> Option<(u64, usize)> {
+    let first = tokens.first()?;
+    if first.norm == "oh" {
+        let unit = tokens.get(1).filter(|_| first.joins_next())?;
+        return match classify(&unit.norm) {
+            Some((NumberWord::Unit(v @ 1..=9), false)) => Some((v, 2)),
+            _ => None,
+        };
+    }
+    match classify(&first.norm)? {
+        (NumberWord::Teen(v), false) => Some((v, 1)),
+        (NumberWord::Tens(v), false) => {
+            let unit = tokens
+                .get(1)
+                .filter(|_| first.joins_next())
+                .and_then(|t| classify(&t.norm));
+            match unit {
+                Some((NumberWord::Unit(u @ 1..=9), false)) => Some((v + u, 2)),
+                _ => Some((v, 1)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Minutes after an hour
+fn parse_minutes(tokens: &[Token]) -> Option<(u64, usize)> {
+    parse_two_digits(tokens).filter(|(v, _)| *v < 60)
+}
+
+/// "three o'clock", "seven pm", "ten thirty a.m."
+fn parse_time(tokens: &[Token]) -> Option<(String, usize)> {
+    let first = tokens.first()?;
+    let hour = match classify(&first.norm)? {
+        (NumberWord::Unit(v @ 1..=9), false) => v,
+        (NumberWord::Teen(v @ 10..=12), false) => v,
+        _ => return None,
+    };
+    if !first.joins_next() {
+        return None;
+    }
+
+    let next = tokens.get(1)?;
+    if next.norm == "oclock" {
+        return Some((format!("{}:00", hour), 2));
+    }
+    if let Some(m) = meridiem(next) {
+        return Some((format!("{} {}", hour, m), 2));
+    }
+
+    let (minutes, len) = parse_minutes(&tokens[1..])?;
+    if !tokens[len].joins_next() {
+        return None;
+    }
+    let m = meridiem(tokens.get(1 + len)?)?;
+    Some((format!("{}:{:02} {}", hour, minutes, m), 2 + len))
+}
+
+/// A year spoken in two halves: "nineteen ninety nine", "twenty oh five"
+fn parse_year_halves(tokens: &[Token]) -> Option<(u64, usize)> {
+    let first = tokens.first()?;
+    let century = match classify(&first.norm)? {
+        (NumberWord::Teen(v @ 11..=19), false) => v,
+        (NumberWord::Tens(20), false) => 20,
+        _ => return None,
+    };
+    if !first.joins_next() {
+        return None;
+    }
+    let (rest, len) = parse_two_digits(&tokens[1..])?;
+    Some((century * 100 + rest, 1 + len))
+}
+
+/// A year after a date: "2024", "twenty twenty four", "two thousand and five"
+fn parse_year(tokens: &[Token]) -> Option<(u64, usize)> {
+    let first = tokens.first()?;
+
+    if first.core.len() == 4 {
+        if let Some((year, None)) = parse_numeric(&first.core) {
+            return Some((year, 1));
+        }
+    }
+    if let Some(year) = parse_year_halves(tokens) {
+        return Some(year);
+    }
+
+    let cardinal = parse_cardinal(tokens)?;
+    (cardinal.len > 1 && !cardinal.ordinal && (1000..3000).contains(&cardinal.value))
+        .then_some((cardinal.value, cardinal.len))
+}
+
+/// Appends ", YYYY" when a year follows the day of a date
+fn with_year(text: String, tokens: &[Token], len: usize) -> (String, usize) {
+    let separator = &tokens[len - 1].trailing;
+    if separator.is_empty() || separator == "," {
+        if let Some((year, year_len)) = parse_year(&tokens[len..]) {
+            return (format!("{}, {}", text, year), len + year_len);
+        }
+    }
+    (text, len)
+}
+
+/// "march third", "May 1st 2024", "the fourth of july". `previous` is the
+/// token before `tokens`, if any.
+fn parse_date(tokens: &[Token], previous: Option<&Token>) -> Option<(String, usize)> {
+    let first = tokens.first()?;
+
+    if let Some(month) = month_index(&first.norm) {
+        if !first.joins_next() {
+            return None;
+        }
+        let day = parse_cardinal(&tokens[1..])?;
+        if !(1..=31).contains(&day.value) || (month == 4 && !day.ordinal) {
+            return None;
+        }
+        let text = format!("{} {}", MONTHS[month], day.value);
+        let (text, len) = with_year(text, tokens, 1 + day.len);
+
+        // "may" is usually a verb ("you may first want to"), so it is only
+        // read as the month after "on", "in" or "the", or before a year
+        let has_date_context = len > 1 + day.len
+            || previous
+                .is_some_and(|t| t.joins_next() && matches!(t.norm.as_str(), "on" | "in" | "the"));
+        if month == 4 && !has_date_context {
+            return None;
+        }
+        return Some((text, len));
+    }
+
+    if first.norm == "the" && first.joins_next() {
+        let day = parse_cardinal(&tokens[1..])?;
+        if !day.ordinal || !(1..=31).contains(&day.value) || !tokens[day.len].joins_next() {
+            return None;
+        }
+        let of = tokens.get(1 + day.len).filter(|t| t.norm == "of")?;
+        if !of.joins_next() {
+            return None;
+        }
+        let month = month_index(&tokens.get(2 + day.len)?.norm)?;
+        let text = format!("{} {}", MONTHS[month], day.value);
+        return Some(with_year(text, tokens, 3 + day.len));
+    }
+
+    None
+}
+
+/// Standalone years that can't be read as a single cardinal
+fn parse_spoken_year(tokens: &[Token]) -> Option<(String, usize)> {
+    parse_year_halves(tokens).map(|(year, len)| (year.to_string(), len))
+}
+
+/// Currency amounts: "five dollars", "twenty three dollars and fifty cents"
+fn parse_currency(number: &Number, symbol: &str, tokens: &[Token]) -> (String, usize) {
+    let unit = &tokens[number.len];
+    let mut text = format!("{}{}", symbol, number.render());
+    let mut len = number.len + 1;
+
+    if number.fraction.is_none() && unit.joins_next() {
+        let rest = &tokens[len..];
+        let skip = match rest.first() {
+            Some(t) if t.norm == "and" && t.joins_next() => 1,
+            _ => 0,
+        };
+        if let Some(cents) = parse_cardinal(&rest[skip..]) {
+            let cents_word = rest.get(skip + cents.len);
+            if !cents.ordinal
+                && cents.value < 100
+                && rest[skip + cents.len - 1].joins_next()
+                && cents_word.is_some_and(|t| t.norm == "cents" || t.norm == "cent")
+            {
+                text = format!("{}.{:02}", text, cents.value);
+                len += skip + cents.len + 1;
+            }
+        }
+    }
+
+    (text, len)
+}
+
+/// Numbers with their units, decimals, ordinals and plain cardinals
+fn convert_number(tokens: &[Token]) -> Option<(String, usize)> {
+    let number = parse_number(tokens)?;
+
+    if !number.ordinal && tokens[number.len - 1].joins_next() {
+        let next = tokens.get(number.len);
+        let after = tokens.get(number.len + 1);
+        match next.map(|t| t.norm.as_str()) {
+            Some("percent") => return Some((format!("{}%", number.render()), number.len + 1)),
+            Some("per")
+                if next.is_some_and(Token::joins_next)
+                    && after.is_some_and(|t| t.norm == "cent") =>
+            {
+                return Some((format!("{}%", number.render()), number.len + 2));
+            }
+            Some("dollars" | "dollar") => return Some(parse_currency(&number, "$", tokens)),
+            Some("euros" | "euro") => return Some(parse_currency(&number, "€", tokens)),
+            Some("cents" | "cent") if number.fraction.is_none() && number.integer < 100 => {
+                return Some((format!("{}¢", number.integer), number.len + 1));
+            }
+            _ => {}
+        }
+    }
+
+    if number.numeric {
+        return None;
+    }
+    if number.fraction.is_some() {
+        return Some((number.render(), number.len));
+    }
+    if number.integer < 10 && number.len == 1 {
+        return None;
+    }
+    if number.ordinal {
+        let text = format!("{}{}", number.integer, ordinal_suffix(number.integer));
+        return Some((text, number.len));
+    }
+    Some((number.render(), number.len))
+}
+
+fn normalize_line(line: &str) -> String {
+    let tokens = tokenize(line);
+    if tokens.is_empty() {
+        return line.to_string();
+    }
+
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let rest = &tokens[i..];
+        let converted = parse_digit_sequence(rest)
+            .or_else(|| parse_time(rest))
+            .or_else(|| parse_date(rest, i.checked_sub(1).map(|p| &tokens[p])))
+            .or_else(|| parse_spoken_year(rest))
+            .or_else(|| convert_number(rest));
+
+        match converted {
+            Some((text, len)) => {
+                out.push(format!(
+                    "{}{}{}",
+                    rest[0].leading,
+                    text,
+                    rest[len - 1].trailing
+                ));
+                i += len;
+            }
+            None => {
+                out.push(rest[0].original());
+                i += 1;
+            }
+        }
+    }
+
+    out.join(" ")
+}
+
+/// Rewrites spoken-form numbers, dates, times and amounts into written form
+///
+/// # Arguments
+/// * `text` - The transcribed text
+/// * `language` - Language code of the text (e.g. "en", "en-GB")
+///
+/// # Returns
+/// The normalized text, or the input unchanged for unsupported languages
+pub fn apply_inverse_text_normalization(text: &str, language: &str) -> String {
+    let base = language.split(['-', '_']).next().unwrap_or("");
+    if !base.eq_ignore_ascii_case("en") {
+        return text.to_string();
+    }
+
+    text.split('\n')
+        .map(normalize_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn itn(text: &str) -> String {
+        apply_inverse_text_normalization(text, "en")
+    }
+
+    #[test]
+    fn converts_currency_and_dates() {
+        assert_eq!(
+            itn("twenty three dollars and fifty cents on march third"),
+            "$23.50 on March 3"
+        );
+        assert_eq!(itn("it costs three million dollars"), "it costs $3,000,000");
+        assert_eq!(itn("about ninety nine euros."), "about €99.");
+        assert_eq!(itn("only fifty cents"), "only 50¢");
+    }
+
+    #[test]
+    fn converts_cardinals() {
+        assert_eq!(
+            itn("there were one hundred and twenty five people"),
+            "there were 125 people"
+        );
+        assert_eq!(itn("twenty-three of them"), "23 of them");
+        assert_eq!(itn("two thousand three hundred"), "2300");
+        assert_eq!(itn("a million reasons"), "1,000,000 reasons");
+        assert_eq!(itn("twenty, thirty, forty"), "20, 30, 40");
+    }
+
+    #[test]
+    fn keeps_small_standalone_numbers() {
+        assert_eq!(
+            itn("I have two cats and one dog"),
+            "I have two cats and one dog"
+        );
+        assert_eq!(itn("at first it was fine"), "at first it was fine");
+        assert_eq!(itn("wait a second"), "wait a second");
+        assert_eq!(itn("you may one day"), "you may one day");
+    }
+
+    #[test]
+    fn converts_ordinals() {
+        assert_eq!(itn("she finished twenty first"), "she finished 21st");
+        assert_eq!(itn("the eleventh hour"), "the 11th hour");
+        assert_eq!(itn("his one hundred second race"), "his 102nd race");
+    }
+
+    #[test]
+    fn converts_decimals_and_percentages() {
+        assert_eq!(itn("pi is three point one four"), "pi is 3.14");
+        assert_eq!(itn("point five"), "0.5");
+        assert_eq!(itn("fifty percent"), "50%");
+        assert_eq!(itn("two point five per cent"), "2.5%");
+        assert_eq!(itn("up 25 percent"), "up 25%");
+        assert_eq!(itn("the point is"), "the point is");
+    }
+
+    #[test]
+    fn converts_dates_and_years() {
+        assert_eq!(
+            itn("you may first want to check"),
+            "you may first want to check"
+        );
+        assert_eq!(itn("due on may first"), "due on May 1");
+        assert_eq!(itn("may first twenty twenty four"), "May 1, 2024");
+        assert_eq!(
+            itn("on the fourth of july, twenty twenty four"),
+            "on July 4, 2024"
+        );
+        assert_eq!(
+            itn("born december twenty fifth nineteen ninety nine"),
+            "born December 25, 1999"
+        );
+        assert_eq!(itn("back in nineteen eighty four"), "back in 1984");
+        assert_eq!(itn("in twenty oh five"), "in 2005");
+    }
+
+    #[test]
+    fn converts_times() {
+        assert_eq!(itn("meet at three thirty pm"), "meet at 3:30 PM");
+        assert_eq!(itn("ten o'clock"), "10:00");
+        assert_eq!(itn("wake me at seven am"), "wake me at 7 AM");
+        assert_eq!(itn("nine oh five a.m."), "9:05 AM.");
+    }
+
+    #[test]
+    fn converts_digit_sequences() {
+        assert_eq!(
+            itn("call five five five one two three four"),
+            "call 555-1234"
+        );
+        assert_eq!(
+            itn("my number is four one five five five five oh one nine nine."),
+            "my number is 415-555-0199."
+        );
+        assert_eq!(itn("one two three go"), "one two three go");
+    }
+
+    #[test]
+    fn preserves_lines_and_other_languages() {
+        assert_eq!(itn("twenty people\nthirty chairs"), "20 people\n30 chairs");
+        let german = "dreiundzwanzig Euro";
+        assert_eq!(apply_inverse_text_normalization(german, "de"), german);
+        assert_eq!(
+            apply_inverse_text_normalization("fifty percent", "en-GB"),
+            "50%"
+        );
+    }
+}
@@ -0,0 +1,946 @@
+mod itn;
+mod spoken_commands;
+
+pub use itn::apply_inverse_text_normalization;
+pub use spoken_commands::format_spoken_commands;
+
+use crate::settings::{ReplacementMatchKind, ReplacementRule};
+use log::warn;
+use natural::phonetics::soundex;
+use once_cell::sync::Lazy;
+use regex::{NoExpand, Regex};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use strsim::levenshtein;
+
+/// Builds an n-gram string by cleaning and concatenating words
+///
+/// Strips punctuation from each word, lowercases, and joins without spaces.
+/// This allows matching "Charge B" against "ChargeBee".
+fn build_ngram(words: &[&str]) -> String {
+    words
+        .iter()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Finds the best matching custom word for a candidate string
+///
+/// Uses Levenshtein distance and Soundex phonetic matching to find
+/// the best match above the given threshold.
+///
+/// # Arguments
+/// * `candidate` - The cleaned/lowercased candidate string to match
+/// * `custom_words` - Original custom words (for returning the replacement)
+/// * `custom_words_nospace` - Custom words with spaces removed, lowercased (for comparison)
+/// * `threshold` - Maximum similarity score to accept
+///
+/// # Returns
+/// The best matching custom word and its score, if any match was found
+fn find_best_match<'a>(
+    candidate: &str,
+    custom_words: &'a [String],
+    custom_words_nospace: &[String],
+    threshold: f64,
+) -> Option<(&'a String, f64)> {
+    if candidate.is_empty() || candidate.len() > 50 {
+        return None;
+    }
+
+    let mut best_match: Option<&String> = None;
+    let mut best_score = f64::MAX;
+
+    for (i, custom_word_nospace) in custom_words_nospace.iter().enumerate() {
+        // Skip if lengths are too different (optimization + prevents over-matching)
+        // Use percentage-based check: max 25% length difference (prevents n-grams from
+        // matching significantly shorter custom words, e.g., "openaigpt" vs "openai")
+        let len_diff = (candidate.len() as i32 - custom_word_nospace.len() as i32).abs() as f64;
+        let max_len = candidate.len().max(custom_word_nospace.len()) as f64;
+        let max_allowed_diff = (max_len * 0.25).max(2.0); // At least 2 chars difference allowed
+        if len_diff > max_allowed_diff {
+            continue;
+        }
+
+        // Calculate Levenshtein distance (normalized by length)
+        let levenshtein_dist = levenshtein(candidate, custom_word_nospace);
+        let max_len = candidate.len().max(custom_word_nospace.len()) as f64;
+        let levenshtein_score = if max_len > 0.0 {
+            levenshtein_dist as f64 / max_len
+        } else {
+            1.0
+        };
+
+        // Calculate phonetic similarity using Soundex
+        let phonetic_match = soundex(candidate, custom_word_nospace);
+
+        // Combine scores: favor phonetic matches, but also consider string similarity
+        let combined_score = if phonetic_match {
+            levenshtein_score * 0.3 // Give significant boost to phonetic matches
+        } else {
+            levenshtein_score
+        };
+
+        // Accept if the score is good enough (configurable threshold)
+        if combined_score < threshold && combined_score < best_score {
+            best_match = Some(&custom_words[i]);
+            best_score = combined_score;
+        }
+    }
+
+    best_match.map(|m| (m, best_score))
+}
+
+/// Applies custom word corrections to transcribed text using fuzzy matching
+///
+/// This function corrects words in the input text by finding the best matches
+/// from a list of custom words using a combination of:
+/// - Levenshtein distance for string similarity
+/// - Soundex phonetic matching for pronunciation similarity
+/// - N-gram matching for multi-word speech artifacts (e.g., "Charge B" -> "ChargeBee")
+///
+/// # Arguments
+/// * `text` - The input text to correct
+/// * `custom_words` - List of custom words to match against
+/// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
+///
+/// # Returns
+/// The corrected text with custom words applied
+pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -> String {
+    if custom_words.is_empty() {
+        return text.to_string();
+    }
+
+    // Pre-compute lowercase versions to avoid repeated allocations
+    let custom_words_lower: Vec<String> = custom_words.iter().map(|w| w.to_lowercase()).collect();
+
+    // Pre-compute versions with spaces removed for n-gram comparison
+    let custom_words_nospace: Vec<String> = custom_words_lower
+        .iter()
+        .map(|w| w.replace(' ', ""))
+        .collect();
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let mut matched = false;
+
+        // Try n-grams from longest (3) to shortest (1) - greedy matching
+        for n in (1..=3).rev() {
+            if i + n > words.len() {
+                continue;
+            }
+
+            let ngram_words = &words[i..i + n];
+            let ngram = build_ngram(ngram_words);
+
+            if let Some((replacement, _score)) =
+                find_best_match(&ngram, custom_words, &custom_words_nospace, threshold)
+            {
+                // Extract punctuation from first and last words of the n-gram
+                let (prefix, _) = extract_punctuation(ngram_words[0]);
+                let (_, suffix) = extract_punctuation(ngram_words[n - 1]);
+
+                // Preserve case from first word
+                let corrected = preserve_case_pattern(ngram_words[0], replacement);
+
+                result.push(format!("{}{}{}", prefix, corrected, suffix));
+                i += n;
+                matched = true;
+                break;
+            }
+        }
+
+        if !matched {
+            result.push(words[i].to_string());
+            i += 1;
+        }
+    }
+
+    result.join(" ")
+}
+
+/// Preserves the case pattern of the original word when applying a replacement
+fn preserve_case_pattern(original: &str, replacement: &str) -> String {
+    if original.chars().all(|c| c.is_uppercase()) {
+        replacement.to_uppercase()
+    } else if original.chars().next().map_or(false, |c| c.is_uppercase()) {
+        let mut chars: Vec<char> = replacement.chars().collect();
+        if let Some(first_char) = chars.get_mut(0) {
+            *first_char = first_char.to_uppercase().next().unwrap_or(*first_char);
+        }
+        chars.into_iter().collect()
+    } else {
+        replacement.to_string()
+    }
+}
+
+/// Extracts punctuation prefix and suffix from a word
+fn extract_punctuation(word: &str) -> (&str, &str) {
+    let prefix_end = word.chars().take_while(|c| !c.is_alphanumeric()).count();
+    let suffix_start = word
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| !c.is_alphanumeric())
+        .count();
+
+    let prefix = if prefix_end > 0 {
+        &word[..prefix_end]
+    } else {
+        ""
+    };
+
+    let suffix = if suffix_start > 0 {
+        &word[word.len() - suffix_start..]
+    } else {
+        ""
+    };
+
+    (prefix, suffix)
+}
+
+/// Compiles a replacement rule into the regex used to apply it
+///
+/// Literal patterns are escaped. Whole-word matching adds `\b` anchors, but
+/// only next to word characters so literals like "@home" still match.
+/// Matching is case-insensitive unless the rule is case-sensitive.
+pub fn build_replacement_regex(rule: &ReplacementRule) -> Result<Regex, regex::Error> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    let body = match rule.match_kind {
+        ReplacementMatchKind::Literal => {
+            let escaped = regex::escape(&rule.pattern);
+            if rule.whole_word {
+                let start = rule.pattern.chars().next().is_some_and(is_word_char);
+                let end = rule.pattern.chars().last().is_some_and(is_word_char);
+                format!(
+                    "{}{}{}",
+                    if start { r"\b" } else { "" },
+                    escaped,
+                    if end { r"\b" } else { "" }
+                )
+            } else {
+                escaped
+            }
+        }
+        ReplacementMatchKind::Regex => {
+            if rule.whole_word {
+                format!(r"\b(?:{})\b", rule.pattern)
+            } else {
+                rule.pattern.clone()
+            }
+        }
+    };
+
+    if rule.case_sensitive {
+        Regex::new(&body)
+    } else {
+        Regex::new(&format!("(?i){}", body))
+    }
+}
+
+/// Expands `\n`, `\t` and `\\` escapes in a replacement string
+fn unescape_replacement(replacement: &str) -> String {
+    let mut result = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Applies find/replace rules to transcribed text, in order
+///
+/// Disabled rules and rules with an empty or invalid pattern are skipped.
+///
+/// # Arguments
+/// * `text` - The input text
+/// * `rules` - Replacement rules from settings
+///
+/// # Returns
+/// The text with every enabled rule applied
+pub fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> String {
+    let mut result = text.to_string();
+
+    for rule in rules.iter().filter(|r| r.enabled && !r.pattern.is_empty()) {
+        let regex = match build_replacement_regex(rule) {
+            Ok(regex) => regex,
+            Err(e) => {
+                warn!(
+                    "Skipping invalid replacement rule '{}': {}",
+                    rule.pattern, e
+                );
+                continue;
+            }
+        };
+
+        let replacement = unescape_replacement(&rule.replacement);
+        result = match rule.match_kind {
+            ReplacementMatchKind::Literal => regex
+                .replace_all(&result, NoExpand(&replacement))
+                .to_string(),
+            ReplacementMatchKind::Regex => {
+                regex.replace_all(&result, replacement.as_str()).to_string()
+            }
+        };
+    }
+
+    result
+}
+
+/// Longest span (in words) considered a vocabulary correction, matching the
+/// largest n-gram `apply_custom_words` tries.
+const MAX_SUBSTITUTION_WORDS: usize = 3;
+
+/// Whether any word has an uppercase letter after its first character
+/// (e.g. "ChargeBee", "iPhone", "NASA")
+fn has_internal_capitals(text: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.chars().skip(1).any(|c| c.is_uppercase()))
+}
+
+/// Joins a run of words, dropping sentence punctuation around the span
+fn join_span(words: &[&str]) -> String {
+    words
+        .join(" ")
+        .trim_matches(|c: char| matches!(c, '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\''))
+        .to_string()
+}
+
+/// Finds the spans of words that were replaced between `original` and `corrected`
+///
+/// Words are compared the same way `build_ngram` cleans them (punctuation
+/// stripped, lowercased) and aligned with a longest-common-subsequence diff.
+/// Each run of differing words becomes a `(from, to)` pair, e.g.
+/// "charge bee" -> "ChargeBee". Pure insertions or deletions, runs longer than
+/// three words, and changes that only affect case or punctuation are ignored,
+/// unless the correction introduces mixed case like "ChargeBee".
+///
+/// # Arguments
+/// * `original` - The text as transcribed
+/// * `corrected` - The text after the user's correction
+///
+/// # Returns
+/// The substituted spans, in order of appearance
+pub fn diff_word_substitutions(original: &str, corrected: &str) -> Vec<(String, String)> {
+    let a: Vec<&str> = original.split_whitespace().collect();
+    let b: Vec<&str> = corrected.split_whitespace().collect();
+    let a_norm: Vec<String> = a.iter().map(|w| build_ngram(&[*w])).collect();
+    let b_norm: Vec<String> = b.iter().map(|w| build_ngram(&[*w])).collect();
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a_norm[i] == b_norm[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut substitutions = Vec::new();
+    let mut push = |from: &[&str], to: &[&str]| {
+        if from.is_empty()
+            || to.is_empty()
+            || from.len() > MAX_SUBSTITUTION_WORDS
+            || to.len() > MAX_SUBSTITUTION_WORDS
+        {
+            return;
+        }
+        let (from, to) = (join_span(from), join_span(to));
+        if from.is_empty() || to.is_empty() || from == to {
+            return;
+        }
+        let same_letters = build_ngram(&from.split_whitespace().collect::<Vec<_>>())
+            == build_ngram(&to.split_whitespace().collect::<Vec<_>>());
+        if same_letters && from.split_whitespace().count() == to.split_whitespace().count() {
+            // Only a case/punctuation change; keep it if it adds mixed case
+            if !has_internal_capitals(&to) || has_internal_capitals(&from) {
+                return;
+            }
+        }
+        substitutions.push((from, to));
+    };
+
+    let (mut i, mut j) = (0, 0);
+    let (mut run_a, mut run_b): (Vec<&str>, Vec<&str>) = (Vec::new(), Vec::new());
+    loop {
+        let at_end = i == a.len() && j == b.len();
+        if at_end || (i < a.len() && j < b.len() && a_norm[i] == b_norm[j]) {
+            push(&run_a, &run_b);
+            run_a.clear();
+            run_b.clear();
+            if at_end {
+                break;
+            }
+            push(&a[i..i + 1], &b[j..j + 1]);
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            run_a.push(a[i]);
+            i += 1;
+        } else {
+            run_b.push(b[j]);
+            j += 1;
+        }
+    }
+
+    substitutions
+}
+
+/// Default English filler words removed from transcriptions
+pub const DEFAULT_FILLER_WORDS: &[&str] = &[
+    "uh", "um", "uhm", "umm", "uhh", "uhhh", "ah", "eh", "hmm", "hm", "mmm", "mm", "mh", "ha",
+    "ehh",
+];
+
+/// Default German filler words removed from transcriptions
+pub const DEFAULT_GERMAN_FILLER_WORDS: &[&str] = &["äh", "ähm", "öh", "öhm", "hm", "hmm", "mhm"];
+
+/// Default Japanese filler words removed from transcriptions
+pub const DEFAULT_JAPANESE_FILLER_WORDS: &[&str] = &[
+    "えーと",
+    "ええと",
+    "えっと",
+    "えー",
+    "あのー",
+    "あの",
+    "うーん",
+];
+
+static MULTI_SPACE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s{2,}").unwrap());
+
+/// Collapses repeated 1-2 letter words (3+ repetitions) to a single instance.
+/// E.g., "wh wh wh wh" -> "wh", "I I I I" -> "I"
+fn collapse_stutters(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return text.to_string();
+    }
+
+    let mut result: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i];
+        let word_lower = word.to_lowercase();
+
+        // Only process 1-2 letter words
+        if word_lower.len() <= 2 && word_lower.chars().all(|c| c.is_alphabetic()) {
+            // Count consecutive repetitions (case-insensitive)
+            let mut count = 1;
+            while i + count < words.len() && words[i + count].to_lowercase() == word_lower {
+                count += 1;
+            }
+
+            // If 3+ repetitions, collapse to single instance
+            if count >= 3 {
+                result.push(word);
+                i += count;
+            } else {
+                result.push(word);
+                i += 1;
+            }
+        } else {
+            result.push(word);
+            i += 1;
+        }
+    }
+
+    result.join(" ")
+}
+
+/// Whether a character belongs to a script written without spaces between words
+fn is_unspaced_script(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{FF66}'..='\u{FF9F}' // Half-width Katakana
+    )
+}
+
+/// Builds a pattern matching a filler word, optionally followed by a comma or period
+///
+/// Word boundaries only exist in spaced scripts. Japanese or Chinese fillers sit
+/// directly against neighbouring characters, so they must be followed by
+/// punctuation, whitespace or the end of the text; otherwise "あの" would also
+/// match the start of "あの人".
+fn build_filler_pattern(word: &str) -> Option<Regex> {
+    let word = word.trim();
+    if word.is_empty() {
+        return None;
+    }
+    let escaped = regex::escape(word);
+    let pattern = if word.chars().any(is_unspaced_script) {
+        format!(r"(?i){}(?:[,.、。]|\s+|$)", escaped)
+    } else {
+        format!(r"(?i)\b{}\b[,.]?", escaped)
+    };
+    match Regex::new(&pattern) {
+        Ok(regex) => Some(regex),
+        Err(e) => {
+            warn!("Skipping invalid filler word '{}': {}", word, e);
+            None
+        }
+    }
+}
+
+/// Patterns compiled for the last filler word list, reused until the list changes
+#[derive(Default)]
+struct FillerPatternCache {
+    words: Vec<String>,
+    patterns: Arc<[Regex]>,
+}
+
+static FILLER_PATTERNS: Lazy<Mutex<FillerPatternCache>> = Lazy::new(Mutex::default);
+
+fn filler_patterns(filler_words: &[String]) -> Arc<[Regex]> {
+    let mut cache = FILLER_PATTERNS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if cache.words != filler_words {
+        cache.patterns = filler_words
+            .iter()
+            .filter_map(|w| build_filler_pattern(w))
+            .collect();
+        cache.words = filler_words.to_vec();
+    }
+    cache.patterns.clone()
+}
+
+/// Looks up the filler words for a language code such as "de" or "en-US"
+///
+/// Tries the exact code first, then the base language. Languages without a
+/// list of their own use the English one, which used to apply to every
+/// language.
+pub fn filler_words_for_language<'a>(
+    filler_words: &'a HashMap<String, Vec<String>>,
+    language: &str,
+) -> &'a [String] {
+    let base = language.split(['-', '_']).next().unwrap_or(language);
+    filler_words
+        .get(language)
+        .or_else(|| filler_words.get(base))
+        .or_else(|| filler_words.get("en"))
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+/// Filters transcription output by removing filler words and stutter artifacts.
+///
+/// This function cleans up raw transcription text by:
+/// 1. Removing the given filler words (uh, um, hmm, etc.)
+/// 2. Collapsing repeated 1-2 letter stutters (e.g., "wh wh wh" -> "wh"), if enabled
+/// 3. Cleaning up excess whitespace
+///
+/// # Arguments
+/// * `text` - The raw transcription text to filter
+/// * `filler_words` - Filler words to remove; pass an empty slice to keep them
+/// * `collapse_repeats` - Whether to collapse stutter artifacts
+///
+/// # Returns
+/// The filtered text with filler words and stutters removed
+pub fn filter_transcription_output(
+    text: &str,
+    filler_words: &[String],
+    collapse_repeats: bool,
+) -> String {
+    let mut filtered = text.to_string();
+
+    // Remove filler words
+    for pattern in filler_patterns(filler_words).iter() {
+        filtered = pattern.replace_all(&filtered, "").to_string();
+    }
+
+    // Collapse repeated 1-2 letter words (stutter artifacts like "wh wh wh wh")
+    if collapse_repeats {
+        filtered = collapse_stutters(&filtered);
+    }
+
+    // Clean up multiple spaces to single space
+    filtered = MULTI_SPACE_PATTERN.replace_all(&filtered, " ").to_string();
+
+    // Trim leading/trailing whitespace
+    filtered.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english_fillers() -> Vec<String> {
+        DEFAULT_FILLER_WORDS.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply_custom_words_exact_match() {
+        let text = "hello world";
+        let custom_words = vec!["Hello".to_string(), "World".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "Hello World");
+    }
+
+    #[test]
+    fn test_apply_custom_words_fuzzy_match() {
+        let text = "helo wrold";
+        let custom_words = vec!["hello".to_string(), "world".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "hello world");
+    }
+
+    #[test]
+    fn test_preserve_case_pattern() {
+        assert_eq!(preserve_case_pattern("HELLO", "world"), "WORLD");
+        assert_eq!(preserve_case_pattern("Hello", "world"), "World");
+        assert_eq!(preserve_case_pattern("hello", "WORLD"), "WORLD");
+    }
+
+    #[test]
+    fn test_extract_punctuation() {
+        assert_eq!(extract_punctuation("hello"), ("", ""));
+        assert_eq!(extract_punctuation("!hello?"), ("!", "?"));
+        assert_eq!(extract_punctuation("...hello..."), ("...", "..."));
+    }
+
+    #[test]
+    fn test_empty_custom_words() {
+        let text = "hello world";
+        let custom_words = vec![];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "hello world");
+    }
+
+    fn rule(pattern: &str, replacement: &str) -> ReplacementRule {
+        ReplacementRule {
+            id: pattern.to_string(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            match_kind: ReplacementMatchKind::Literal,
+            whole_word: true,
+            case_sensitive: false,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_replacement_rules_literal() {
+        let rules = vec![rule("at handy dot computer", "@handy.computer")];
+        let result = apply_replacement_rules("email me At Handy dot computer today", &rules);
+        assert_eq!(result, "email me @handy.computer today");
+    }
+
+    #[test]
+    fn test_replacement_rules_newline_escape() {
+        let rules = vec![rule(" new line ", "\\n")];
+        let result = apply_replacement_rules("first item new line second item", &rules);
+        assert_eq!(result, "first item\nsecond item");
+    }
+
+    #[test]
+    fn test_replacement_rules_whole_word() {
+        let rules = vec![rule("cat", "dog")];
+        assert_eq!(
+            apply_replacement_rules("cat concatenate", &rules),
+            "dog concatenate"
+        );
+
+        let mut anywhere = rule("cat", "dog");
+        anywhere.whole_word = false;
+        assert_eq!(
+            apply_replacement_rules("cat concatenate", &[anywhere]),
+            "dog condogenate"
+        );
+    }
+
+    #[test]
+    fn test_replacement_rules_case_sensitive() {
+        let mut sensitive = rule("handy", "Handy");
+        sensitive.case_sensitive = true;
+        assert_eq!(
+            apply_replacement_rules("handy HANDY", &[sensitive]),
+            "Handy HANDY"
+        );
+    }
+
+    #[test]
+    fn test_replacement_rules_regex_with_captures() {
+        let mut regex_rule = rule(r"(\d+) percent", "$1%");
+        regex_rule.match_kind = ReplacementMatchKind::Regex;
+        assert_eq!(
+            apply_replacement_rules("about 40 percent done", &[regex_rule]),
+            "about 40% done"
+        );
+    }
+
+    #[test]
+    fn test_replacement_rules_literal_does_not_expand_captures() {
+        let rules = vec![rule("dollars", "$1")];
+        assert_eq!(apply_replacement_rules("five dollars", &rules), "five $1");
+    }
+
+    #[test]
+    fn test_replacement_rules_skips_disabled_and_invalid() {
+        let mut disabled = rule("hello", "bye");
+        disabled.enabled = false;
+        let mut invalid = rule("(unclosed", "x");
+        invalid.match_kind = ReplacementMatchKind::Regex;
+        assert_eq!(
+            apply_replacement_rules("hello (unclosed", &[disabled, invalid]),
+            "hello (unclosed"
+        );
+    }
+
+    #[test]
+    fn test_diff_word_substitutions_finds_merged_words() {
+        let subs = diff_word_substitutions(
+            "we moved billing to charge bee last week.",
+            "We moved billing to ChargeBee last week.",
+        );
+        assert_eq!(
+            subs,
+            vec![("charge bee".to_string(), "ChargeBee".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_diff_word_substitutions_ignores_case_and_punctuation() {
+        let subs = diff_word_substitutions("hello world", "Hello, world!");
+        assert!(subs.is_empty());
+    }
+
+    #[test]
+    fn test_diff_word_substitutions_keeps_mixed_case_fixes() {
+        let subs = diff_word_substitutions("my iphone died", "my iPhone died");
+        assert_eq!(subs, vec![("iphone".to_string(), "iPhone".to_string())]);
+    }
+
+    #[test]
+    fn test_diff_word_substitutions_ignores_insertions_and_long_rewrites() {
+        assert!(diff_word_substitutions("see you soon", "see you very soon").is_empty());
+        assert!(diff_word_substitutions(
+            "this sentence was completely different before",
+            "nothing here matches the old text at all"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_filter_filler_words() {
+        let text = "So um I was thinking uh about this";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "So I was thinking about this");
+    }
+
+    #[test]
+    fn test_filter_filler_words_case_insensitive() {
+        let text = "UM this is UH a test";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "this is a test");
+    }
+
+    #[test]
+    fn test_filter_filler_words_with_punctuation() {
+        let text = "Well, um, I think, uh. that's right";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "Well, I think, that's right");
+    }
+
+    #[test]
+    fn test_filter_cleans_whitespace() {
+        let text = "Hello    world   test";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "Hello world test");
+    }
+
+    #[test]
+    fn test_filter_trims() {
+        let text = "  Hello world  ";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "Hello world");
+    }
+
+    #[test]
+    fn test_filter_combined() {
+        let text = "  Um, so I was, uh, thinking about this  ";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "so I was, thinking about this");
+    }
+
+    #[test]
+    fn test_filter_preserves_valid_text() {
+        let text = "This is a completely normal sentence.";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "This is a completely normal sentence.");
+    }
+
+    #[test]
+    fn test_filter_stutter_collapse() {
+        let text = "w wh wh wh wh wh wh wh wh wh why";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "w wh why");
+    }
+
+    #[test]
+    fn test_filter_stutter_short_words() {
+        let text = "I I I I think so so so so";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "I think so");
+    }
+
+    #[test]
+    fn test_filter_stutter_mixed_case() {
+        let text = "No NO no NO no";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "No");
+    }
+
+    #[test]
+    fn test_filter_stutter_preserves_two_repetitions() {
+        let text = "no no is fine";
+        let result = filter_transcription_output(text, &english_fillers(), true);
+        assert_eq!(result, "no no is fine");
+    }
+
+    #[test]
+    fn test_filter_keeps_fillers_when_list_empty() {
+        let text = "So um I was thinking uh about this";
+        let result = filter_transcription_output(text, &[], true);
+        assert_eq!(result, text);
+    }
+
+    #[test]
+    fn test_filter_stutters_kept_when_disabled() {
+        let text = "I I I I think um so";
+        let result = filter_transcription_output(text, &english_fillers(), false);
+        assert_eq!(result, "I I I I think so");
+    }
+
+    #[test]
+    fn test_filter_german_fillers() {
+        let fillers = vec!["äh".to_string(), "ähm".to_string()];
+        let text = "Also ähm, ich glaube äh das passt";
+        let result = filter_transcription_output(text, &fillers, true);
+        assert_eq!(result, "Also ich glaube das passt");
+    }
+
+    #[test]
+    fn test_filter_japanese_fillers_without_spaces() {
+        let fillers = vec!["えーと".to_string(), "あの".to_string()];
+        let text = "えーと、今日はあの、会議があります";
+        let result = filter_transcription_output(text, &fillers, true);
+        assert_eq!(result, "今日は会議があります");
+    }
+
+    #[test]
+    fn test_japanese_filler_needs_a_delimiter_after_it() {
+        let fillers: Vec<String> = DEFAULT_JAPANESE_FILLER_WORDS
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert_eq!(
+            filter_transcription_output("あの人に会いました", &fillers, true),
+            "あの人に会いました"
+        );
+        assert_eq!(
+            filter_transcription_output("あの、あの人に会いました。えーと", &fillers, true),
+            "あの人に会いました。"
+        );
+    }
+
+    #[test]
+    fn test_filler_words_for_language_falls_back_to_base() {
+        let mut map = HashMap::new();
+        map.insert("en".to_string(), vec!["um".to_string()]);
+        map.insert("pt-BR".to_string(), vec!["tipo".to_string()]);
+        assert_eq!(filler_words_for_language(&map, "en-US"), ["um".to_string()]);
+        assert_eq!(
+            filler_words_for_language(&map, "pt-BR"),
+            ["tipo".to_string()]
+        );
+        assert_eq!(filler_words_for_language(&map, "pt"), ["um".to_string()]);
+        assert_eq!(filler_words_for_language(&map, "de"), ["um".to_string()]);
+
+        // An empty list turns filtering off for that language only
+        map.insert("de".to_string(), Vec::new());
+        assert!(filler_words_for_language(&map, "de").is_empty());
+    }
+
+    #[test]
+    fn test_apply_custom_words_ngram_two_words() {
+        let text = "il cui nome è Charge B, che permette";
+        let custom_words = vec!["ChargeBee".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert!(result.contains("ChargeBee,"));
+        assert!(!result.contains("Charge B"));
+    }
+
+    #[test]
+    fn test_apply_custom_words_ngram_three_words() {
+        let text = "use Chat G P T for this";
+        let custom_words = vec!["ChatGPT".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert!(result.contains("ChatGPT"));
+    }
+
+    #[test]
+    fn test_apply_custom_words_prefers_longer_ngram() {
+        let text = "Open AI GPT model";
+        let custom_words = vec!["OpenAI".to_string(), "GPT".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert_eq!(result, "OpenAI GPT model");
+    }
+
+    #[test]
+    fn test_apply_custom_words_ngram_preserves_case() {
+        let text = "CHARGE B is great";
+        let custom_words = vec!["ChargeBee".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert!(result.contains("CHARGEBEE"));
+    }
+
+    #[test]
+    fn test_apply_custom_words_ngram_with_spaces_in_custom() {
+        // Custom word with space should also match against split words
+        let text = "using Mac Book Pro";
+        let custom_words = vec!["MacBook Pro".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        assert!(result.contains("MacBook"));
+    }
+
+    #[test]
+    fn test_apply_custom_words_trailing_number_not_doubled() {
+        // Verify that trailing non-alpha chars (like numbers) aren't double-counted
+        // between build_ngram stripping them and extract_punctuation capturing them
+        let text = "use GPT4 for this";
+        let custom_words = vec!["GPT-4".to_string()];
+        let result = apply_custom_words(text, &custom_words, 0.5);
+        // Should NOT produce "GPT-44" (double-counting the trailing 4)
+        assert!(
+            !result.contains("GPT-44"),
+            "got double-counted result: {}",
+            result
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Spoken punctuation and formatting commands.
+//!
+//! Turns dictated commands like "comma", "new paragraph" or "open quote" into
+//! the punctuation and layout they describe. Each language has its own command
+//! table; text in a language without a table is returned unchanged.
+
+/// What a spoken command produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    /// Punctuation attached to the previous word, e.g. "," or "?"
+    Punctuation(&'static str),
+    NewLine,
+    NewParagraph,
+    OpenQuote,
+    CloseQuote,
+    OpenParen,
+    CloseParen,
+    BulletPoint,
+}
+
+/// Longest command phrase, in words, across all tables
+const MAX_COMMAND_WORDS: usize = 3;
+
+const ENGLISH_COMMANDS: &[(&str, Command)] = &[
+    ("comma", Command::Punctuation(",")),
+    ("period", Command::Punctuation(".")),
+    ("full stop", Command::Punctuation(".")),
+    ("question mark", Command::Punctuation("?")),
+    ("exclamation mark", Command::Punctuation("!")),
+    ("exclamation point", Command::Punctuation("!")),
+    ("colon", Command::Punctuation(":")),
+    ("semicolon", Command::Punctuation(";")),
+    ("ellipsis", Command::Punctuation("...")),
+    ("new line", Command::NewLine),
+    ("newline", Command::NewLine),
+    ("new paragraph", Command::NewParagraph),
+    ("open quote", Command::OpenQuote),
+    ("begin quote", Command::OpenQuote),
+    ("close quote", Command::CloseQuote),
+    ("end quote", Command::CloseQuote),
+    ("unquote", Command::CloseQuote),
+    ("open paren", Command::OpenParen),
+    ("open parenthesis", Command::OpenParen),
+    ("close paren", Command::CloseParen),
+    ("close parenthesis", Command::CloseParen),
+    ("bullet point", Command::BulletPoint),
+];
+
+const GERMAN_COMMANDS: &[(&str, Command)] = &[
+    ("komma", Command::Punctuation(",")),
+    ("punkt", Command::Punctuation(".")),
+    ("fragezeichen", Command::Punctuation("?")),
+    ("ausrufezeichen", Command::Punctuation("!")),
+    ("doppelpunkt", Command::Punctuation(":")),
+    ("semikolon", Command::Punctuation(";")),
+    ("neue zeile", Command::NewLine),
+    ("neuer absatz", Command::NewParagraph),
+    ("anführungszeichen auf", Command::OpenQuote),
+    ("anführungszeichen unten", Command::OpenQuote),
+    ("anführungszeichen zu", Command::CloseQuote),
+    ("anführungszeichen oben", Command::CloseQuote),
+    ("klammer auf", Command::OpenParen),
+    ("klammer zu", Command::CloseParen),
+    ("aufzählungspunkt", Command::BulletPoint),
+];
+
+const SPANISH_COMMANDS: &[(&str, Command)] = &[
+    ("coma", Command::Punctuation(",")),
+    ("punto", Command::Punctuation(".")),
+    ("punto final", Command::Punctuation(".")),
+    ("signo de interrogación", Command::Punctuation("?")),
+    ("signo de exclamación", Command::Punctuation("!")),
+    ("dos puntos", Command::Punctuation(":")),
+    ("punto y coma", Command::Punctuation(";")),
+    ("nueva línea", Command::NewLine),
+    ("nuevo párrafo", Command::NewParagraph),
+    ("abrir comillas", Command::OpenQuote),
+    ("cerrar comillas", Command::CloseQuote),
+    ("abrir paréntesis", Command::OpenParen),
+    ("cerrar paréntesis", Command::CloseParen),
+    ("viñeta", Command::BulletPoint),
+];
+
+/// Command table for a language code such as "en", "de-AT" or "es"
+fn commands_for_language(language: &str) -> Option<&'static [(&'static str, Command)]> {
+    let base = language.split(['-', '_']).next().unwrap_or("");
+    match base.to_lowercase().as_str() {
+        "en" => Some(ENGLISH_COMMANDS),
+        "de" => Some(GERMAN_COMMANDS),
+        "es" => Some(SPANISH_COMMANDS),
+        _ => None,
+    }
+}
+
+/// Lowercases a word and strips punctuation the engine may have added around it
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Finds the longest command starting at `words[0]`, returning it and its length
+fn match_command(words: &[&str], table: &[(&str, Command)]) -> Option<(Command, usize)> {
+    for n in (1..=MAX_COMMAND_WORDS.min(words.len())).rev() {
+        let candidate = words[..n]
+            .iter()
+            .map(|w| normalize(w))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Some((_, command)) = table.iter().find(|(phrase, _)| *phrase == candidate) {
+            return Some((*command, n));
+        }
+    }
+    None
+}
+
+/// Accumulates formatted output and tracks spacing/capitalization state
+#[derive(Default)]
+struct Formatter {
+    out: String,
+    no_space_next: bool,
+    capitalize_next: bool,
+}
+
+impl Formatter {
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn trim_trailing_spaces(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+    }
+
+    fn push_word(&mut self, word: &str) {
+        if !self.at_line_start() && !self.no_space_next {
+            self.out.push(' ');
+        }
+        if self.capitalize_next {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                self.out.extend(first.to_uppercase());
+                self.out.push_str(chars.as_str());
+            }
+        } else {
+            self.out.push_str(word);
+        }
+        self.no_space_next = false;
+        self.capitalize_next = false;
+    }
+
+    fn push_punctuation(&mut self, punctuation: &str) {
+        self.trim_trailing_spaces();
+        // Replace punctuation the engine already put on the previous word
+        let trimmed = self
+            .out
+            .trim_end_matches(['.', ',', ';', ':', '?', '!'])
+            .len();
+        self.out.truncate(trimmed);
+        self.out.push_str(punctuation);
+        self.no_space_next = false;
+        if matches!(punctuation, "." | "?" | "!") {
+            self.capitalize_next = true;
+        }
+    }
+
+    fn push_break(&mut self, breaks: &str) {
+        self.trim_trailing_spaces();
+        if !self.out.is_empty() {
+            self.out.push_str(breaks);
+        }
+        self.no_space_next = true;
+        self.capitalize_next = true;
+    }
+
+    fn apply(&mut self, command: Command) {
+        match command {
+            Command::Punctuation(p) => self.push_punctuation(p),
+            Command::NewLine => self.push_break("\n"),
+            Command::NewParagraph => self.push_break("\n\n"),
+            Command::BulletPoint => {
+                self.trim_trailing_spaces();
+                if !self.at_line_start() {
+                    self.out.push('\n');
+                }
+                self.out.push_str("- ");
+                self.no_space_next = true;
+                self.capitalize_next = true;
+            }
+            Command::OpenQuote | Command::OpenParen => {
+                if !self.at_line_start() && !self.no_space_next {
+                    self.out.push(' ');
+                }
+                self.out.push(if command == Command::OpenQuote {
+                    '"'
+                } else {
+                    '('
+                });
+                self.no_space_next = true;
+            }
+            Command::CloseQuote | Command::CloseParen => {
+                self.trim_trailing_spaces();
+                self.out.push(if command == Command::CloseQuote {
+                    '"'
+                } else {
+                    ')'
+                });
+                self.no_space_next = false;
+            }
+        }
+    }
+}
+
+/// Replaces spoken punctuation and formatting commands with the text they describe
+///
+/// # Arguments
+/// * `text` - The transcribed text
+/// * `language` - Language code used to pick the command table (e.g. "en", "de-DE")
+///
+/// # Returns
+/// The formatted text, or the input unchanged if the language has no command table
+pub fn format_spoken_commands(text: &str, language: &str) -> String {
+    let Some(table) = commands_for_language(language) else {
+        return text.to_string();
+    };
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut formatter = Formatter::default();
+    let mut i = 0;
+
+    while i < words.len() {
+        match match_command(&words[i..], table) {
+            Some((command, len)) => {
+                formatter.apply(command);
+                i += len;
+            }
+            None => {
+                formatter.push_word(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    formatter.trim_trailing_spaces();
+    formatter.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_punctuation() {
+        assert_eq!(
+            format_spoken_commands("hello comma how are you question mark fine period", "en"),
+            "hello, how are you? Fine."
+        );
+    }
+
+    #[test]
+    fn replaces_engine_punctuation_on_previous_word() {
+        assert_eq!(
+            format_spoken_commands("Thanks, comma. see you period.", "en-US"),
+            "Thanks, see you."
+        );
+    }
+
+    #[test]
+    fn english_layout_commands() {
+        assert_eq!(
+            format_spoken_commands(
+                "notes colon bullet point first item bullet point second item new paragraph thanks",
+                "en"
+            ),
+            "notes:\n- First item\n- Second item\n\nThanks"
+        );
+    }
+
+    #[test]
+    fn english_quotes_and_parentheses() {
+        assert_eq!(
+            format_spoken_commands(
+                "he said open quote ship it close quote open paren today close paren",
+                "en"
+            ),
+            "he said \"ship it\" (today)"
+        );
+    }
+
+    #[test]
+    fn german_commands() {
+        assert_eq!(
+            format_spoken_commands(
+                "hallo komma wie geht es fragezeichen neue zeile gut punkt",
+                "de"
+            ),
+            "hallo, wie geht es?\nGut."
+        );
+    }
+
+    #[test]
+    fn spanish_multi_word_commands() {
+        assert_eq!(
+            format_spoken_commands("lista dos puntos uno punto y coma dos", "es"),
+            "lista: uno; dos"
+        );
+    }
+
+    #[test]
+    fn unsupported_language_is_unchanged() {
+        let text = "bonjour virgule ça va";
+        assert_eq!(format_spoken_commands(text, "fr"), text);
+    }
+}
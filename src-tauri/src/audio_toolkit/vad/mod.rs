@@ -25,8 +25,10 @@ pub trait VoiceActivityDetector: Send + Sync {
     fn reset(&mut self) {}
 }
 
+mod segmenter;
 mod silero;
 mod smoothed;
 
+pub use segmenter::split_on_silence;
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use std::ops::Range;
+
+use super::VoiceActivityDetector;
+use crate::audio_toolkit::constants;
+
+/// Frame size the VAD is fed with (30 ms at 16 kHz)
+const SEGMENT_FRAME_SAMPLES: usize = (constants::WHISPER_SAMPLE_RATE * 30 / 1000) as usize;
+
+/// Shortest run of silent frames (300 ms) considered a safe place to cut
+const MIN_SILENCE_FRAMES: usize = 10;
+
+/// Splits `samples` into chunks no longer than `max_segment_samples`, cutting
+/// in the middle of pauses detected by `vad` wherever possible.
+///
+/// Chunks that contain no speech at all are dropped. Returns sample ranges
+/// into `samples`, in order.
+pub fn split_on_silence(
+    samples: &[f32],
+    vad: &mut dyn VoiceActivityDetector,
+    max_segment_samples: usize,
+) -> Result<Vec<Range<usize>>> {
+    vad.reset();
+
+    let mut speech_flags = Vec::with_capacity(samples.len() / SEGMENT_FRAME_SAMPLES + 1);
+    let mut padded = vec![0.0; SEGMENT_FRAME_SAMPLES];
+    for frame in samples.chunks(SEGMENT_FRAME_SAMPLES) {
+        let is_speech = if frame.len() == SEGMENT_FRAME_SAMPLES {
+            vad.is_voice(frame)?
+        } else {
+            padded[..frame.len()].copy_from_slice(frame);
+            padded[frame.len()..].fill(0.0);
+            vad.is_voice(&padded)?
+        };
+        speech_flags.push(is_speech);
+    }
+
+    let max_frames = (max_segment_samples / SEGMENT_FRAME_SAMPLES).max(1);
+    Ok(plan_segments(&speech_flags, max_frames)
+        .into_iter()
+        .map(|frames| {
+            let start = frames.start * SEGMENT_FRAME_SAMPLES;
+            let end = (frames.end * SEGMENT_FRAME_SAMPLES).min(samples.len());
+            start..end
+        })
+        .collect())
+}
+
+/// Groups per-frame speech decisions into frame ranges of at most `max_frames`.
+///
+/// A segment is only cut once it reaches the maximum length, at the middle of
+/// the latest pause long enough to count, so segments stay as long as the
+/// engine allows. Without such a pause the segment is cut hard.
+fn plan_segments(speech_flags: &[bool], max_frames: usize) -> Vec<Range<usize>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut silence_start: Option<usize> = None;
+    let mut cut_candidate: Option<usize> = None;
+
+    let mut i = 0;
+    while i < speech_flags.len() {
+        if speech_flags[i] {
+            if let Some(s) = silence_start.take() {
+                if i - s >= MIN_SILENCE_FRAMES && s > start {
+                    cut_candidate = Some(s + (i - s) / 2);
+                }
+            }
+        } else if silence_start.is_none() {
+            silence_start = Some(i);
+        }
+
+        if i + 1 - start >= max_frames {
+            // An ongoing pause that is already long enough beats an earlier one
+            let ongoing = silence_start
+                .filter(|&s| i + 1 - s >= MIN_SILENCE_FRAMES && s > start)
+                .map(|s| s + (i + 1 - s) / 2);
+            let cut = ongoing.or(cut_candidate).unwrap_or(i + 1);
+
+            segments.push(start..cut);
+            start = cut;
+            cut_candidate = None;
+            silence_start = None;
+            // Re-scan from the cut so the remainder's pauses are tracked
+            i = cut;
+            continue;
+        }
+        i += 1;
+    }
+    if start < speech_flags.len() {
+        segments.push(start..speech_flags.len());
+    }
+
+    segments.retain(|range| speech_flags[range.clone()].iter().any(|&s| s));
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::vad::VadFrame;
+
+    /// Treats any frame with a non-zero sample as speech
+    struct EnergyVad;
+
+    impl VoiceActivityDetector for EnergyVad {
+        fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+            if frame.iter().any(|&s| s != 0.0) {
+                Ok(VadFrame::Speech(frame))
+            } else {
+                Ok(VadFrame::Noise)
+            }
+        }
+    }
+
+    fn flags(pattern: &str) -> Vec<bool> {
+        pattern.chars().map(|c| c == 's').collect()
+    }
+
+    #[test]
+    fn short_audio_is_one_segment() {
+        let speech = flags("sssss.....sssss");
+        assert_eq!(plan_segments(&speech, 100), vec![0..15]);
+    }
+
+    #[test]
+    fn cuts_in_middle_of_latest_pause() {
+        // Two pauses: one at 5..15 and a later one at 20..32
+        let speech = flags("sssss..........sssss............ssssssss");
+        assert_eq!(plan_segments(&speech, 35), vec![0..26, 26..40]);
+    }
+
+    #[test]
+    fn ignores_pauses_that_are_too_short() {
+        let speech = flags("sssss.....ssssssss");
+        // The 5-frame pause is too short, so the cut falls at the limit
+        assert_eq!(plan_segments(&speech, 12), vec![0..12, 12..18]);
+    }
+
+    #[test]
+    fn drops_silent_segments() {
+        let mut speech = flags("sssss");
+        speech.extend(std::iter::repeat_n(false, 40));
+        speech.extend(flags("sssss"));
+        let segments = plan_segments(&speech, 20);
+        assert!(segments.iter().all(|r| speech[r.clone()].contains(&true)));
+        assert_eq!(segments.first().map(|r| r.start), Some(0));
+        assert_eq!(segments.last().map(|r| r.end), Some(50));
+    }
+
+    #[test]
+    fn split_on_silence_returns_sample_ranges() {
+        let frame = SEGMENT_FRAME_SAMPLES;
+        let mut samples = vec![0.5; frame * 20];
+        samples.extend(vec![0.0; frame * 20]);
+        samples.extend(vec![0.5; frame * 20 + 100]);
+
+        let segments = split_on_silence(&samples, &mut EnergyVad, frame * 45).unwrap();
+        assert_eq!(segments, vec![0..frame * 30, frame * 30..samples.len()]);
+    }
+}
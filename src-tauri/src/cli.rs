@@ -1,4 +1,5 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone, Default)]
 #[command(name = "handy", about = "Handy - Speech to Text")]
@@ -26,4 +27,54 @@ pub struct CliArgs {
     /// Enable debug mode with verbose logging
     #[arg(long)]
     pub debug: bool,
+
+    /// Export transcription history to a file and exit
+    #[arg(long, value_name = "PATH")]
+    pub export_history: Option<PathBuf>,
+
+    /// Format for --export-history: jsonl, csv, markdown, srt or vtt (default: jsonl)
+    #[arg(long, value_name = "FORMAT", requires = "export_history")]
+    pub format: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CliCommand {
+    /// Transcribe audio files and print the result without opening any windows
+    Transcribe(TranscribeArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TranscribeArgs {
+    /// Audio files to transcribe
+    #[arg(required = true, value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+
+    /// Model to use instead of the one selected in settings
+    #[arg(long, value_name = "ID")]
+    pub model: Option<String>,
+
+    /// Language code such as "en", or "auto" (default: the language in settings)
+    #[arg(long, value_name = "CODE")]
+    pub language: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TranscribeFormat::Txt)]
+    pub format: TranscribeFormat,
+
+    /// Run the text through the post-processing prompt with this ID
+    #[arg(long = "post-process", value_name = "PROMPT_ID")]
+    pub post_process: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscribeFormat {
+    /// Plain text
+    Txt,
+    /// One JSON object per file, with segment timings
+    Json,
+    /// SubRip subtitles
+    Srt,
 }
@@ -1,34 +1,38 @@
-use crate::audio_toolkit::decode_audio_file;
-use crate::managers::history::HistoryManager;
+use crate::audio_toolkit::{decode_audio_file, SUPPORTED_EXTENSIONS};
+use crate::managers::batch::{BatchFile, BatchOutput, BatchStatus, BatchTranscriptionManager};
+use crate::managers::history::{HistoryManager, TranscriptionMetadata, TranscriptionSegment};
 use crate::managers::transcription::TranscriptionManager;
 use log::{error, info};
 use serde::Serialize;
 use specta::Type;
-use std::path::Path;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
 
-const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "m4a", "aac", "ogg", "oga"];
-
 #[derive(Serialize, Type)]
 pub struct FileTranscriptionResult {
     pub text: String,
     pub file_name: String,
     pub duration_ms: u64,
+    pub segments: Vec<TranscriptionSegment>,
 }
 
 #[derive(Clone, Serialize, Type)]
 pub struct FileTranscriptionProgress {
     pub stage: String,
     pub message: Option<String>,
+    /// Share of the audio transcribed so far, from 0 to 100
+    pub percent: Option<f32>,
 }
 
-fn emit_progress(app: &AppHandle, stage: &str, message: Option<&str>) {
+fn emit_progress(app: &AppHandle, stage: &str, message: Option<&str>, percent: Option<f32>) {
     let _ = app.emit(
         "file-transcription-progress",
         FileTranscriptionProgress {
             stage: stage.to_string(),
             message: message.map(|s| s.to_string()),
+            percent,
         },
     );
 }
@@ -72,7 +76,7 @@ pub async fn transcribe_audio_file(
     info!("Starting file transcription: {}", file_name);
 
     // Stage 1: Decode audio file
-    emit_progress(&app, "decoding", None);
+    emit_progress(&app, "decoding", None, None);
     let path_owned = path.to_path_buf();
     let samples = tokio::task::spawn_blocking(move || decode_audio_file(&path_owned))
         .await
@@ -80,24 +84,42 @@ pub async fn transcribe_audio_file(
         .map_err(|e| format!("Failed to decode audio file: {}", e))?;
 
     // Stage 2: Ensure model is loaded
-    emit_progress(&app, "loading_model", None);
+    emit_progress(&app, "loading_model", None, None);
     transcription_manager.initiate_model_load();
 
-    // Stage 3: Transcribe
-    emit_progress(&app, "transcribing", None);
+    // Stage 3: Transcribe, segment by segment for long files
+    emit_progress(&app, "transcribing", None, Some(0.0));
     let start = std::time::Instant::now();
     let tm = transcription_manager.inner().clone();
-    let samples_for_transcription = samples.clone();
-    let text = tokio::task::spawn_blocking(move || tm.transcribe(samples_for_transcription))
-        .await
-        .map_err(|e| format!("Transcription task failed: {}", e))?
-        .map_err(|e| format!("Transcription failed: {}", e))?;
+    let progress_app = app.clone();
+    let (samples, transcription) = tokio::task::spawn_blocking(move || {
+        let result = tm.transcribe_long_form(&samples, None, |percent| {
+            emit_progress(&progress_app, "transcribing", None, Some(percent));
+            ControlFlow::Continue(())
+        });
+        (samples, result)
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e))?;
+    let transcription = transcription.map_err(|e| format!("Transcription failed: {}", e))?;
+    let text = transcription.text.clone();
     let duration_ms = start.elapsed().as_millis() as u64;
 
     // Stage 4: Save to history
-    emit_progress(&app, "saving", None);
+    emit_progress(&app, "saving", None, None);
+    let metadata = TranscriptionMetadata {
+        transcription_ms: Some(duration_ms as i64),
+        ..transcription.metadata.clone()
+    };
     if let Err(e) = history_manager
-        .save_transcription(samples, text.clone(), None, None)
+        .save_transcription(
+            samples,
+            text.clone(),
+            None,
+            None,
+            metadata,
+            transcription.timings(),
+        )
         .await
     {
         error!("Failed to save file transcription to history: {}", e);
@@ -113,5 +135,45 @@ pub async fn transcribe_audio_file(
         text,
         file_name,
         duration_ms,
+        segments: transcription.segments,
     })
 }
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_batch_transcription(
+    batch_manager: State<'_, Arc<BatchTranscriptionManager>>,
+    paths: Vec<String>,
+    output: BatchOutput,
+) -> Result<Vec<BatchFile>, String> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    batch_manager
+        .submit(&paths, output)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn pause_batch_transcription(batch_manager: State<'_, Arc<BatchTranscriptionManager>>) {
+    batch_manager.pause();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn resume_batch_transcription(batch_manager: State<'_, Arc<BatchTranscriptionManager>>) {
+    batch_manager.resume();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_batch_transcription(batch_manager: State<'_, Arc<BatchTranscriptionManager>>) {
+    batch_manager.cancel();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_batch_transcription_status(
+    batch_manager: State<'_, Arc<BatchTranscriptionManager>>,
+) -> BatchStatus {
+    batch_manager.status()
+}
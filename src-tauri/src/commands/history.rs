@@ -1,5 +1,18 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::actions::post_process_transcription;
+use crate::audio_toolkit::decode_audio_file;
+use crate::managers::history::corrections::{CorrectionSuggestion, CorrectionSuggestionKind};
+use crate::managers::history::export::HistoryExportFormat;
+use crate::managers::history::{
+    HistoryEntry, HistoryManager, HistoryPage, HistoryQuery, HistoryRevision, HistoryRevisionKind,
+    HistorySearchResult, TranscriptionMetadata,
+};
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, write_settings};
+use log::info;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 use tauri::{AppHandle, State};
 
 #[tauri::command]
@@ -14,6 +27,256 @@ pub async fn get_history_entries(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn query_history_entries(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: HistoryQuery,
+) -> Result<HistoryPage, String> {
+    history_manager
+        .query_history(&query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_history(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<HistorySearchResult>, String> {
+    history_manager
+        .search_history(&query, limit, offset)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+    format: HistoryExportFormat,
+    ids: Option<Vec<i64>>,
+) -> Result<usize, String> {
+    history_manager
+        .export_history(&PathBuf::from(path), format, ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_archive(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+) -> Result<usize, String> {
+    history_manager
+        .export_archive(&PathBuf::from(path))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_history_archive(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+) -> Result<usize, String> {
+    history_manager
+        .import_archive(&PathBuf::from(path))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_history_entry_text(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    text: String,
+) -> Result<HistoryEntry, String> {
+    history_manager
+        .update_entry_text(id, &text)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_correction_suggestions(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Vec<CorrectionSuggestion>, String> {
+    history_manager
+        .get_correction_suggestions()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn accept_correction_suggestion(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: String,
+) -> Result<(), String> {
+    let suggestion = history_manager
+        .get_correction_suggestions()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Correction suggestion '{}' not found", id))?;
+
+    let mut settings = get_settings(&app);
+    match suggestion.kind {
+        CorrectionSuggestionKind::CustomWord => {
+            if !settings
+                .custom_words
+                .iter()
+                .any(|w| w.eq_ignore_ascii_case(&suggestion.to))
+            {
+                settings.custom_words.push(suggestion.to.clone());
+            }
+        }
+        CorrectionSuggestionKind::ReplacementRule => {
+            settings
+                .replacement_rules
+                .push(suggestion.to_replacement_rule());
+        }
+    }
+    write_settings(&app, settings);
+
+    info!(
+        "Accepted correction suggestion '{}' -> '{}'",
+        suggestion.from, suggestion.to
+    );
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reject_correction_suggestion(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    if !settings.rejected_correction_suggestions.contains(&id) {
+        settings.rejected_correction_suggestions.push(id);
+    }
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_entry_revisions(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<HistoryRevision>, String> {
+    history_manager.get_revisions(id).map_err(|e| e.to_string())
+}
+
+/// Run an entry's stored recording through a model again and save the result
+/// as a new revision, with `model_id` or the selected model. A model other
+/// than the loaded one is loaded just for this run and the previous model is
+/// restored afterwards; that is refused while a dictation is in progress.
+/// `post_process` defaults to whether the original entry was post-processed.
+#[tauri::command]
+#[specta::specta]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    id: i64,
+    model_id: Option<String>,
+    post_process: Option<bool>,
+) -> Result<HistoryRevision, String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+
+    let audio_path = history_manager.get_audio_file_path(&entry.file_name);
+    if !audio_path.exists() {
+        return Err(format!(
+            "Recording for history entry {} is no longer available",
+            id
+        ));
+    }
+
+    let samples = tokio::task::spawn_blocking(move || decode_audio_file(&audio_path))
+        .await
+        .map_err(|e| format!("Decode task failed: {}", e))?
+        .map_err(|e| format!("Failed to decode recording: {}", e))?;
+
+    let model_id = model_id.unwrap_or_else(|| get_settings(&app).selected_model);
+    let audio_duration_ms = entry.metadata.audio_duration_ms;
+    let tm = transcription_manager.inner().clone();
+    let (text, mut metadata) = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        tm.with_model(&model_id, || {
+            let start = Instant::now();
+            let output = tm.transcribe_long_form(&samples, None, |_| ControlFlow::Continue(()))?;
+            let metadata = TranscriptionMetadata {
+                transcription_ms: Some(start.elapsed().as_millis() as i64),
+                audio_duration_ms,
+                ..output.metadata
+            };
+            Ok((output.text, metadata))
+        })
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e))?
+    .map_err(|e| format!("Transcription failed: {}", e))?;
+
+    let mut post_processed_text = None;
+    let mut post_process_prompt = None;
+    if post_process.unwrap_or(entry.post_process_prompt.is_some()) {
+        let settings = get_settings(&app);
+        let start = Instant::now();
+        if let Some(processed) = post_process_transcription(&settings, &text).await {
+            metadata.post_process_ms = Some(start.elapsed().as_millis() as i64);
+            if let Some(provider) = settings.active_post_process_provider() {
+                metadata.post_process_model =
+                    settings.post_process_models.get(&provider.id).cloned();
+                metadata.post_process_provider = Some(provider.id.clone());
+            }
+            post_process_prompt = settings
+                .post_process_selected_prompt_id
+                .as_ref()
+                .and_then(|prompt_id| {
+                    settings
+                        .post_process_prompts
+                        .iter()
+                        .find(|p| &p.id == prompt_id)
+                })
+                .map(|prompt| prompt.prompt.clone());
+            post_processed_text = Some(processed);
+        }
+    }
+
+    let revision = history_manager
+        .add_revision(
+            id,
+            HistoryRevisionKind::Retranscription,
+            text,
+            post_processed_text,
+            post_process_prompt,
+            &metadata,
+        )
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        "Re-transcribed history entry {} with model {:?}",
+        id, revision.metadata.model_id
+    );
+
+    Ok(revision)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn toggle_history_entry_saved(
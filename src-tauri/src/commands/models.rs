@@ -1,6 +1,8 @@
 use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::model_integrity::ModelVerification;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -33,6 +35,19 @@ pub async fn download_model(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn import_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    source: String,
+) -> Result<ModelInfo, String> {
+    model_manager
+        .inner()
+        .import_model(&source)
+        .await
+        .map_err(|e| format!("{:#}", e))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_model(
@@ -65,10 +80,25 @@ pub async fn set_active_model(
     model_manager: State<'_, Arc<ModelManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
     model_id: String,
+) -> Result<(), String> {
+    activate_model(
+        &app_handle,
+        &model_manager,
+        &transcription_manager,
+        &model_id,
+    )
+}
+
+/// Loads a downloaded model and makes it the selected one.
+pub fn activate_model(
+    app_handle: &AppHandle,
+    model_manager: &ModelManager,
+    transcription_manager: &TranscriptionManager,
+    model_id: &str,
 ) -> Result<(), String> {
     // Check if model exists and is available
     let model_info = model_manager
-        .get_model_info(&model_id)
+        .get_model_info(model_id)
         .ok_or_else(|| format!("Model not found: {}", model_id))?;
 
     if !model_info.is_downloaded {
@@ -77,17 +107,57 @@ pub async fn set_active_model(
 
     // Load the model in the transcription manager
     transcription_manager
-        .load_model(&model_id)
+        .load_model(model_id)
         .map_err(|e| e.to_string())?;
 
     // Update settings
-    let mut settings = get_settings(&app_handle);
-    settings.selected_model = model_id.clone();
-    write_settings(&app_handle, settings);
+    let mut settings = get_settings(app_handle);
+    settings.selected_model = model_id.to_string();
+    write_settings(app_handle, settings);
 
     Ok(())
 }
 
+/// Fetches the model catalog from the configured `model_catalog_url`
+/// and returns the updated model list.
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_model_catalog(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelInfo>, String> {
+    let url = get_settings(&app_handle)
+        .model_catalog_url
+        .ok_or_else(|| "No model catalog URL configured".to_string())?;
+    model_manager
+        .refresh_catalog(&url)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    Ok(model_manager.get_available_models())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_errors(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<HashMap<String, String>, String> {
+    Ok(model_manager.get_model_errors())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<ModelVerification, String> {
+    // Hashing a large model takes a while, keep it off the async runtime
+    let model_manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || model_manager.verify_model(&model_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("{:#}", e))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_current_model(app_handle: AppHandle) -> Result<String, String> {
@@ -0,0 +1,366 @@
+//! Unix domain socket for scripting the running instance.
+//!
+//! Clients send newline-delimited JSON-RPC 2.0 requests and get one response
+//! line per request. Recording commands go through the
+//! [`TranscriptionCoordinator`], so they behave exactly like the shortcuts.
+//! After `subscribe`, the coordinator's events are pushed on the same
+//! connection as `event` notifications.
+
+use crate::commands::models::activate_model;
+use crate::managers::history::HistoryManager;
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use crate::shortcut::set_post_process_selected_prompt;
+use crate::transcription_coordinator::{is_transcribe_binding, Transcript};
+use crate::utils::cancel_current_operation;
+use crate::TranscriptionCoordinator;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tauri::{AppHandle, Manager};
+
+/// Directory holding the socket, readable only by the user. Sockets are
+/// created with the process umask, so restricting the file after `bind`
+/// would leave a window where others could connect.
+const SOCKET_DIR_NAME: &str = "control";
+const SOCKET_FILE_NAME: &str = "handy.sock";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Any failure reported by the app itself
+const APP_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        Self::new(APP_ERROR, message)
+    }
+}
+
+/// Where the socket is created: `control/handy.sock` in the app data directory
+pub fn socket_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SOCKET_DIR_NAME).join(SOCKET_FILE_NAME))
+}
+
+/// Creates `dir` if needed and makes it accessible to the user only
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)?;
+    // `mode` doesn't apply to a directory that already existed
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+}
+
+/// Binds the control socket and serves clients on background threads.
+pub fn start(app: &AppHandle) {
+    let Some(path) = socket_path(app) else {
+        warn!("Control socket disabled: app data directory is unavailable");
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(e) = create_private_dir(dir) {
+            warn!(
+                "Control socket disabled: failed to prepare {}: {}",
+                dir.display(),
+                e
+            );
+            return;
+        }
+    }
+
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            warn!(
+                "Control socket {} is in use by another process",
+                path.display()
+            );
+            return;
+        }
+        // Left behind by an instance that didn't shut down cleanly
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Failed to bind control socket {}: {}", path.display(), e);
+            return;
+        }
+    };
+    if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+        warn!("Failed to restrict control socket permissions: {}", e);
+    }
+    info!("Control socket listening on {}", path.display());
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    thread::spawn(move || serve(app, stream));
+                }
+                Err(e) => warn!("Control socket failed to accept a connection: {}", e),
+            }
+        }
+    });
+}
+
+fn serve(app: AppHandle, stream: UnixStream) {
+    let writer = match stream.try_clone() {
+        Ok(writer) => Arc::new(Mutex::new(writer)),
+        Err(e) => {
+            warn!("Control socket failed to clone a connection: {}", e);
+            return;
+        }
+    };
+    let mut subscribed = false;
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (id, result) = match parse_request(&line) {
+            Ok(request) => {
+                debug!("Control socket request: {}", request.method);
+                let result = if request.method == "subscribe" {
+                    if !subscribed {
+                        subscribed = true;
+                        forward_events(&app, writer.clone());
+                    }
+                    Ok(Value::Bool(true))
+                } else {
+                    dispatch(&app, &request.method, &request.params)
+                };
+                match request.id {
+                    Some(id) => (id, result),
+                    None => continue,
+                }
+            }
+            Err(e) => (Value::Null, Err(e)),
+        };
+
+        if write_line(&writer, &response(id, result)).is_err() {
+            break;
+        }
+    }
+}
+
+fn parse_request(line: &str) -> Result<RpcRequest, RpcError> {
+    let value: Value =
+        serde_json::from_str(line).map_err(|e| RpcError::new(PARSE_ERROR, e.to_string()))?;
+    let request: RpcRequest =
+        serde_json::from_value(value).map_err(|e| RpcError::new(INVALID_REQUEST, e.to_string()))?;
+    if request.jsonrpc != "2.0" {
+        return Err(RpcError::new(INVALID_REQUEST, "Expected jsonrpc 2.0"));
+    }
+    Ok(request)
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+fn write_line(writer: &Mutex<UnixStream>, value: &Value) -> std::io::Result<()> {
+    let mut writer = writer.lock().unwrap();
+    writeln!(writer, "{}", value)?;
+    writer.flush()
+}
+
+/// Pushes coordinator events to the client until it disconnects
+fn forward_events(app: &AppHandle, writer: Arc<Mutex<UnixStream>>) {
+    let Some(coordinator) = app.try_state::<TranscriptionCoordinator>() else {
+        return;
+    };
+    let events = coordinator.subscribe();
+    thread::spawn(move || {
+        for event in events {
+            let notification = json!({ "jsonrpc": "2.0", "method": "event", "params": event });
+            if write_line(&writer, &notification).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn dispatch(app: &AppHandle, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let coordinator = app
+        .try_state::<TranscriptionCoordinator>()
+        .ok_or_else(|| RpcError::new(APP_ERROR, "Transcription coordinator not initialized"))?;
+
+    match method {
+        "start_recording" => {
+            let binding_id = optional_str(params, "binding_id")?.unwrap_or("transcribe");
+            if !is_transcribe_binding(binding_id) {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Not a transcription binding: {}", binding_id),
+                ));
+            }
+            coordinator.start_recording(binding_id)?;
+            to_value(coordinator.status()?)
+        }
+        "stop_recording" => {
+            coordinator.stop_recording()?;
+            to_value(coordinator.status()?)
+        }
+        "cancel" => {
+            cancel_current_operation(app);
+            to_value(coordinator.status()?)
+        }
+        "get_status" => {
+            let settings = get_settings(app);
+            let tm = app.state::<Arc<TranscriptionManager>>();
+            Ok(json!({
+                "stage": coordinator.status()?,
+                "loaded_model": tm.get_current_model(),
+                "selected_model": settings.selected_model,
+                "selected_prompt_id": settings.post_process_selected_prompt_id,
+            }))
+        }
+        "get_last_transcript" => {
+            let transcript = match coordinator.last_transcript()? {
+                Some(transcript) => Some(transcript),
+                // Nothing dictated since launch; fall back to history
+                None => app
+                    .state::<Arc<HistoryManager>>()
+                    .get_latest_entry()
+                    .map_err(|e| e.to_string())?
+                    .map(|entry| Transcript {
+                        binding_id: entry.metadata.binding_id.unwrap_or_default(),
+                        text: entry.transcription_text,
+                        post_processed_text: entry.post_processed_text,
+                        timestamp: entry.timestamp,
+                    }),
+            };
+            to_value(transcript)
+        }
+        "set_model" => {
+            let model_id = required_str(params, "model_id")?;
+            activate_model(
+                app,
+                &app.state::<Arc<ModelManager>>(),
+                &app.state::<Arc<TranscriptionManager>>(),
+                model_id,
+            )?;
+            Ok(Value::Null)
+        }
+        "set_prompt" => {
+            let prompt_id = required_str(params, "prompt_id")?;
+            set_post_process_selected_prompt(app.clone(), prompt_id.to_string())?;
+            Ok(Value::Null)
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {}", method),
+        )),
+    }
+}
+
+fn optional_str<'a>(params: &'a Value, key: &str) -> Result<Option<&'a str>, RpcError> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.as_str())),
+        Some(_) => Err(RpcError::new(
+            INVALID_PARAMS,
+            format!("'{}' must be a string", key),
+        )),
+    }
+}
+
+fn required_str<'a>(params: &'a Value, key: &str) -> Result<&'a str, RpcError> {
+    optional_str(params, key)?
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Missing '{}'", key)))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(APP_ERROR, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requests_and_notifications() {
+        let request = parse_request(
+            r#"{"jsonrpc":"2.0","id":7,"method":"set_model","params":{"model_id":"small"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(required_str(&request.params, "model_id").unwrap(), "small");
+
+        let notification = parse_request(r#"{"jsonrpc":"2.0","method":"cancel"}"#).unwrap();
+        assert_eq!(notification.id, None);
+        assert_eq!(notification.params, Value::Null);
+    }
+
+    #[test]
+    fn reports_protocol_errors() {
+        assert_eq!(parse_request("{").unwrap_err().code, PARSE_ERROR);
+        assert_eq!(
+            parse_request(r#"{"jsonrpc":"1.0","id":1,"method":"cancel"}"#)
+                .unwrap_err()
+                .code,
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            required_str(&json!({}), "prompt_id").unwrap_err().code,
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn formats_responses() {
+        assert_eq!(
+            response(json!(1), Ok(json!(true))),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": true })
+        );
+        assert_eq!(
+            response(json!(2), Err(RpcError::new(METHOD_NOT_FOUND, "nope"))),
+            json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": -32601, "message": "nope" } })
+        );
+    }
+}
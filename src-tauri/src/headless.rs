@@ -0,0 +1,223 @@
+//! `handy transcribe` and `--export-history`: run from the command line
+//! without opening any windows, the tray or global shortcuts.
+//!
+//! The managers run on Tauri's mock runtime, configured like the app, so they
+//! read settings and find models and history exactly as the app does. That
+//! runtime has no windowing backend, so no display server is needed. Neither
+//! command touches a running instance; both report their result and exit
+//! status in the invoking process.
+
+use crate::actions::post_process_transcription;
+use crate::audio_toolkit::decode_audio_file;
+use crate::cli::{TranscribeArgs, TranscribeFormat};
+use crate::managers::history::export::{write_segment_subtitles, HistoryExportFormat};
+use crate::managers::history::{HistoryManager, TranscriptionOutput};
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+use tauri::{App, AppHandle, Manager};
+
+/// One line of `--format json` output
+#[derive(Serialize)]
+struct FileTranscription<'a> {
+    file: String,
+    #[serde(flatten)]
+    output: &'a TranscriptionOutput,
+    post_processed_text: Option<&'a str>,
+}
+
+/// Transcribes every file, then exits with 0 if all of them succeeded and 1
+/// otherwise.
+pub fn run(args: TranscribeArgs, context: tauri::Context<tauri::Wry>) {
+    let code = match mock_app(context).and_then(|app| transcribe_files(app.handle(), &args)) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("handy: {}", e);
+            1
+        }
+    };
+    std::process::exit(code);
+}
+
+/// Handles `--export-history` by reading the history database directly,
+/// then exits with 0 on success and 1 otherwise.
+pub fn export_history(path: &Path, format: Option<&str>, context: tauri::Context<tauri::Wry>) {
+    let result = format
+        .map(|f| f.parse::<HistoryExportFormat>())
+        .transpose()
+        .map(|format| format.unwrap_or(HistoryExportFormat::Jsonl))
+        .and_then(|format| {
+            let app_data_dir = mock_app(context)?
+                .path()
+                .app_data_dir()
+                .map_err(|e| e.to_string())?;
+            HistoryManager::export_from_data_dir(&app_data_dir, path, format)
+                .map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(count) => {
+            println!("Exported {} history entries to {}", count, path.display());
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Failed to export history: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Builds the app on the mock runtime. The identifier and package info
+/// decide where settings, models, history and resources are found.
+fn mock_app(context: tauri::Context<tauri::Wry>) -> Result<App<MockRuntime>, String> {
+    let mut mock = mock_context(noop_assets());
+    *mock.config_mut() = context.config().clone();
+    mock.config_mut().app.windows.clear();
+    *mock.package_info_mut() = context.package_info().clone();
+
+    mock_builder()
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .build(mock)
+        .map_err(|e| e.to_string())
+}
+
+/// Transcribes `args.files` in order, printing each result as soon as it is
+/// ready. Returns whether every file succeeded; per-file errors go to stderr.
+fn transcribe_files(
+    app_handle: &AppHandle<MockRuntime>,
+    args: &TranscribeArgs,
+) -> Result<bool, String> {
+    let mut settings = get_settings(app_handle);
+
+    if let Some(prompt_id) = &args.post_process {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Unknown post-processing prompt: {}", prompt_id));
+        }
+        settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+    }
+
+    let model_id = args
+        .model
+        .clone()
+        .unwrap_or_else(|| settings.selected_model.clone());
+    if model_id.is_empty() {
+        return Err("No model selected. Pass --model or choose one in the app.".to_string());
+    }
+
+    let model_manager = Arc::new(ModelManager::new(app_handle).map_err(|e| e.to_string())?);
+    if model_manager.get_model_info(&model_id).is_none() {
+        return Err(format!("Unknown model: {}", model_id));
+    }
+    let transcription_manager =
+        Arc::new(TranscriptionManager::new(app_handle, model_manager).map_err(|e| e.to_string())?);
+    if let Some(language) = &args.language {
+        settings.selected_language = language.clone();
+    }
+
+    let show_headers = args.files.len() > 1 && args.format != TranscribeFormat::Json;
+    let mut all_succeeded = true;
+    for (index, path) in args.files.iter().enumerate() {
+        let result = transcribe_file(
+            &transcription_manager,
+            &model_id,
+            args.language.as_deref(),
+            path,
+        )
+        .and_then(|output| {
+            let post_processed = match &args.post_process {
+                Some(_) => {
+                    let processed = tauri::async_runtime::block_on(post_process_transcription(
+                        &settings,
+                        &output.text,
+                    ));
+                    if processed.is_none() {
+                        eprintln!(
+                            "handy: post-processing failed for {}, printing the raw text",
+                            path.display()
+                        );
+                    }
+                    processed
+                }
+                None => None,
+            };
+
+            let mut stdout = io::stdout().lock();
+            if show_headers {
+                if index > 0 {
+                    writeln!(stdout).map_err(|e| e.to_string())?;
+                }
+                writeln!(stdout, "==> {} <==", path.display()).map_err(|e| e.to_string())?;
+            }
+            print_output(
+                &mut stdout,
+                path,
+                &output,
+                post_processed.as_deref(),
+                args.format,
+            )
+            .map_err(|e| e.to_string())
+        });
+
+        if let Err(e) = result {
+            eprintln!("handy: {}: {}", path.display(), e);
+            all_succeeded = false;
+        }
+    }
+
+    Ok(all_succeeded)
+}
+
+fn transcribe_file(
+    tm: &TranscriptionManager<MockRuntime>,
+    model_id: &str,
+    language: Option<&str>,
+    path: &Path,
+) -> Result<TranscriptionOutput, String> {
+    let samples = decode_audio_file(path).map_err(|e| e.to_string())?;
+
+    // The idle timeout may have unloaded the model since the previous file
+    if !tm.is_model_loaded() {
+        tm.load_model(model_id).map_err(|e| e.to_string())?;
+    }
+
+    tm.transcribe_long_form(&samples, language, |_| ControlFlow::Continue(()))
+        .map_err(|e| e.to_string())
+}
+
+fn print_output<W: Write>(
+    writer: &mut W,
+    path: &Path,
+    output: &TranscriptionOutput,
+    post_processed: Option<&str>,
+    format: TranscribeFormat,
+) -> anyhow::Result<()> {
+    match format {
+        TranscribeFormat::Txt => {
+            writeln!(writer, "{}", post_processed.unwrap_or(&output.text))?;
+        }
+        TranscribeFormat::Json => {
+            let line = FileTranscription {
+                file: path.to_string_lossy().to_string(),
+                output,
+                post_processed_text: post_processed,
+            };
+            serde_json::to_writer(&mut *writer, &line)?;
+            writeln!(writer)?;
+        }
+        TranscribeFormat::Srt => {
+            write_segment_subtitles(writer, &output.segments, HistoryExportFormat::Srt)?;
+        }
+    }
+    Ok(())
+}
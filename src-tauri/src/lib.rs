@@ -1,4 +1,5 @@
 mod actions;
+mod api_server;
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 mod apple_intelligence;
 mod audio_feedback;
@@ -6,6 +7,9 @@ pub mod audio_toolkit;
 pub mod cli;
 mod clipboard;
 mod commands;
+#[cfg(unix)]
+mod control_socket;
+mod headless;
 mod helpers;
 mod input;
 mod llm_client;
@@ -15,18 +19,23 @@ mod settings;
 mod shortcut;
 mod signal_handle;
 mod transcription_coordinator;
+mod transcription_hooks;
 mod tray;
 mod tray_i18n;
 mod utils;
 
 pub use cli::CliArgs;
+use cli::CliCommand;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri_specta::{collect_commands, Builder};
 
+use api_server::ApiServer;
 use env_filter::Builder as EnvFilterBuilder;
 use managers::audio::AudioRecordingManager;
+use managers::batch::BatchTranscriptionManager;
 use managers::history::HistoryManager;
 use managers::model::ModelManager;
+use managers::streaming::StreamingTranscriptionManager;
 use managers::transcription::TranscriptionManager;
 #[cfg(unix)]
 use signal_hook::consts::{SIGUSR1, SIGUSR2};
@@ -120,14 +129,44 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         TranscriptionManager::new(app_handle, model_manager.clone())
             .expect("Failed to initialize transcription manager"),
     );
+    let streaming_manager = Arc::new(StreamingTranscriptionManager::new(
+        app_handle,
+        transcription_manager.clone(),
+    ));
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
+    let batch_manager = Arc::new(BatchTranscriptionManager::new(
+        app_handle,
+        transcription_manager.clone(),
+        history_manager.clone(),
+    ));
 
     // Add managers to Tauri's managed state
     app_handle.manage(recording_manager.clone());
     app_handle.manage(model_manager.clone());
     app_handle.manage(transcription_manager.clone());
+    app_handle.manage(streaming_manager.clone());
     app_handle.manage(history_manager.clone());
+    app_handle.manage(batch_manager.clone());
+
+    // Pick up catalog changes published since this release, in the background
+    if let Some(url) = settings::get_settings(app_handle).model_catalog_url {
+        let model_manager = model_manager.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = model_manager.refresh_catalog(&url).await {
+                log::warn!("Failed to refresh model catalog: {:#}", e);
+            }
+        });
+    }
+
+    let api_server = Arc::new(ApiServer::new(
+        app_handle,
+        transcription_manager.clone(),
+        model_manager.clone(),
+    ));
+    // A failure to bind is reported through `model-state-changed`
+    let _ = api_server.apply_settings();
+    app_handle.manage(api_server);
 
     // Note: Shortcuts are NOT initialized here.
     // The frontend is responsible for calling the `initialize_shortcuts` command
@@ -139,6 +178,9 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     // Set up signal handlers for toggling transcription
     #[cfg(unix)]
     signal_handle::setup_signal_handler(app_handle.clone(), signals);
+    // Set up the JSON-RPC control socket for scripting
+    #[cfg(unix)]
+    control_socket::start(app_handle);
 
     // Apply macOS Accessory policy if starting hidden
     #[cfg(target_os = "macos")]
@@ -251,6 +293,17 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run(cli_args: CliArgs) {
+    let context = tauri::generate_context!();
+
+    if let Some(CliCommand::Transcribe(args)) = cli_args.command.clone() {
+        headless::run(args, context);
+        return;
+    }
+    if let Some(path) = cli_args.export_history.as_deref() {
+        headless::export_history(path, cli_args.format.as_deref(), context);
+        return;
+    }
+
     // Parse console logging directives from RUST_LOG, falling back to info-level logging
     // when the variable is unset
     let console_filter = build_console_filter();
@@ -288,10 +341,26 @@ pub fn run(cli_args: CliArgs) {
         shortcut::delete_post_process_prompt,
         shortcut::set_post_process_selected_prompt,
         shortcut::update_custom_words,
+        shortcut::update_replacement_rules,
         shortcut::suspend_binding,
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
         shortcut::change_append_trailing_space_setting,
+        shortcut::change_filter_filler_words_setting,
+        shortcut::update_filler_words,
+        shortcut::change_collapse_stutters_setting,
+        shortcut::change_spoken_punctuation_setting,
+        shortcut::change_inverse_text_normalization_setting,
+        shortcut::change_streaming_transcription_setting,
+        shortcut::change_api_server_enabled_setting,
+        shortcut::change_api_server_port_setting,
+        shortcut::change_api_server_token_setting,
+        shortcut::change_transcription_webhook_url_setting,
+        shortcut::change_transcription_hook_command_setting,
+        shortcut::change_transcription_hook_timeout_setting,
+        shortcut::change_model_catalog_url_setting,
+        shortcut::change_models_dir_setting,
+        shortcut::change_model_search_paths_setting,
         shortcut::change_app_language_setting,
         shortcut::change_update_checks_setting,
         shortcut::change_keyboard_implementation_setting,
@@ -315,9 +384,13 @@ pub fn run(cli_args: CliArgs) {
         commands::models::get_available_models,
         commands::models::get_model_info,
         commands::models::download_model,
+        commands::models::import_model,
         commands::models::delete_model,
         commands::models::cancel_download,
         commands::models::set_active_model,
+        commands::models::refresh_model_catalog,
+        commands::models::verify_model,
+        commands::models::get_model_errors,
         commands::models::get_current_model,
         commands::models::get_transcription_model_status,
         commands::models::is_model_loading,
@@ -340,12 +413,28 @@ pub fn run(cli_args: CliArgs) {
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
         commands::history::get_history_entries,
+        commands::history::query_history_entries,
+        commands::history::search_history,
+        commands::history::export_history,
+        commands::history::export_history_archive,
+        commands::history::import_history_archive,
+        commands::history::update_history_entry_text,
+        commands::history::get_history_entry_revisions,
+        commands::history::get_correction_suggestions,
+        commands::history::accept_correction_suggestion,
+        commands::history::reject_correction_suggestion,
+        commands::history::retranscribe_history_entry,
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
         commands::file_transcription::transcribe_audio_file,
+        commands::file_transcription::start_batch_transcription,
+        commands::file_transcription::pause_batch_transcription,
+        commands::file_transcription::resume_batch_transcription,
+        commands::file_transcription::cancel_batch_transcription,
+        commands::file_transcription::get_batch_transcription_status,
         helpers::clamshell::is_laptop,
     ]);
 
@@ -429,6 +518,7 @@ pub fn run(cli_args: CliArgs) {
             // Store the file log level in the atomic for the filter to use
             FILE_LOG_LEVEL.store(file_log_level.to_level_filter() as u8, Ordering::Relaxed);
             let app_handle = app.handle().clone();
+
             app.manage(TranscriptionCoordinator::new(app_handle.clone()));
 
             initialize_core_logic(&app_handle);
@@ -479,6 +569,6 @@ pub fn run(cli_args: CliArgs) {
             _ => {}
         })
         .invoke_handler(specta_builder.invoke_handler())
-        .run(tauri::generate_context!())
+        .run(context)
         .expect("error while running tauri application");
 }
@@ -3,7 +3,7 @@ use crate::helpers::clamshell;
 use crate::settings::{get_settings, AppSettings};
 use crate::utils;
 use log::{debug, error, info};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
 use tauri::Manager;
 
@@ -112,11 +112,15 @@ pub enum MicrophoneMode {
     OnDemand,
 }
 
+/// Where speech segments go while a streaming transcription is running
+type SegmentSink = Arc<Mutex<Option<mpsc::Sender<Vec<f32>>>>>;
+
 /* ──────────────────────────────────────────────────────────────── */
 
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
+    segment_sink: SegmentSink,
 ) -> Result<AudioRecorder, anyhow::Error> {
     let silero = SileroVad::new(vad_path, 0.3)
         .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
@@ -132,6 +136,11 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_segment_callback(move |segment| {
+            if let Some(tx) = segment_sink.lock().unwrap().as_ref() {
+                let _ = tx.send(segment);
+            }
         });
 
     Ok(recorder)
@@ -149,6 +158,7 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+    segment_sink: SegmentSink,
 }
 
 impl AudioRecordingManager {
@@ -171,6 +181,7 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+            segment_sink: Arc::new(Mutex::new(None)),
         };
 
         // Always-on?  Open immediately.
@@ -261,6 +272,7 @@ impl AudioRecordingManager {
             *recorder_opt = Some(create_audio_recorder(
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
+                self.segment_sink.clone(),
             )?);
         }
 
@@ -297,6 +309,7 @@ impl AudioRecordingManager {
             // If still recording, stop first.
             if *self.is_recording.lock().unwrap() {
                 let _ = rec.stop();
+                self.set_segment_sink(None);
                 *self.is_recording.lock().unwrap() = false;
             }
             let _ = rec.close();
@@ -332,6 +345,12 @@ impl AudioRecordingManager {
 
     /* ---------- recording --------------------------------------------------- */
 
+    /// Streams speech segments of the next recording to `sink` as they end.
+    /// The sink is dropped when that recording stops or is cancelled.
+    pub fn set_segment_sink(&self, sink: Option<mpsc::Sender<Vec<f32>>>) {
+        *self.segment_sink.lock().unwrap() = sink;
+    }
+
     pub fn try_start_recording(&self, binding_id: &str) -> bool {
         let mut state = self.state.lock().unwrap();
 
@@ -393,6 +412,8 @@ impl AudioRecordingManager {
                     Vec::new()
                 };
 
+                // The last segment was delivered during stop(); close the stream
+                self.set_segment_sink(None);
                 *self.is_recording.lock().unwrap() = false;
 
                 // In on-demand mode turn the mic off again
@@ -433,6 +454,7 @@ impl AudioRecordingManager {
                 let _ = rec.stop(); // Discard the result
             }
 
+            self.set_segment_sink(None);
             *self.is_recording.lock().unwrap() = false;
 
             // In on-demand mode turn the mic off again
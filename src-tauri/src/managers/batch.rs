@@ -0,0 +1,435 @@
+use crate::audio_toolkit::{decode_audio_file, is_supported_audio_file};
+use crate::managers::history::export::{write_segment_subtitles, HistoryExportFormat};
+use crate::managers::history::{HistoryManager, TranscriptionMetadata};
+use crate::managers::transcription::TranscriptionManager;
+use anyhow::{Context, Result};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+/// Where the result of a batch transcription goes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchOutput {
+    /// Plain text file next to the source
+    Txt,
+    /// Subtitles file next to the source, one cue per segment
+    Srt,
+    /// A new history entry
+    History,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchFileStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct BatchFile {
+    pub path: String,
+    pub output: BatchOutput,
+    pub status: BatchFileStatus,
+    /// Share of the file transcribed so far, from 0 to 100
+    pub percent: f32,
+    /// Sidecar file written for `txt` and `srt` output
+    pub output_path: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct BatchStatus {
+    pub files: Vec<BatchFile>,
+    pub running: bool,
+    pub paused: bool,
+}
+
+/// Emitted as `batch-transcription-progress` whenever a file changes
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct BatchProgressEvent {
+    pub index: usize,
+    pub total: usize,
+    pub file: BatchFile,
+}
+
+#[derive(Default)]
+struct BatchQueue {
+    files: Vec<BatchFile>,
+    running: bool,
+    paused: bool,
+    /// Set by `cancel` to stop the file currently being transcribed
+    cancel_current: bool,
+}
+
+/// Transcribes queued audio files one after another on a background thread.
+///
+/// Files are transcribed with the loaded model via
+/// `TranscriptionManager::transcribe_long_form`, which shares the engine with
+/// dictation one segment at a time. Pausing takes effect between segments of
+/// a file, so the engine stays free for dictation while paused.
+#[derive(Clone)]
+pub struct BatchTranscriptionManager {
+    app_handle: AppHandle,
+    transcription_manager: Arc<TranscriptionManager>,
+    history_manager: Arc<HistoryManager>,
+    queue: Arc<(Mutex<BatchQueue>, Condvar)>,
+}
+
+impl BatchTranscriptionManager {
+    pub fn new(
+        app_handle: &AppHandle,
+        transcription_manager: Arc<TranscriptionManager>,
+        history_manager: Arc<HistoryManager>,
+    ) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            transcription_manager,
+            history_manager,
+            queue: Arc::new((Mutex::new(BatchQueue::default()), Condvar::new())),
+        }
+    }
+
+    /// Queues the supported audio files among `paths`, searching directories
+    /// recursively, and starts the worker if it isn't running.
+    /// Returns the files that were queued.
+    pub fn submit(&self, paths: &[PathBuf], output: BatchOutput) -> Result<Vec<BatchFile>> {
+        let found = collect_audio_files(paths)?;
+        if found.is_empty() {
+            anyhow::bail!("No supported audio files found");
+        }
+
+        let added: Vec<BatchFile> = found
+            .into_iter()
+            .map(|path| BatchFile {
+                path: path.to_string_lossy().to_string(),
+                output,
+                status: BatchFileStatus::Queued,
+                percent: 0.0,
+                output_path: None,
+                error: None,
+            })
+            .collect();
+
+        let (lock, _) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        if !queue.running {
+            // Results of the previous batch were already reported
+            queue.files.clear();
+        }
+        queue.files.extend(added.iter().cloned());
+        info!("Queued {} files for batch transcription", added.len());
+
+        if !queue.running {
+            queue.running = true;
+            let worker = self.clone();
+            thread::spawn(move || worker.run());
+        }
+
+        Ok(added)
+    }
+
+    pub fn pause(&self) {
+        let (lock, cvar) = &*self.queue;
+        lock.lock().unwrap().paused = true;
+        cvar.notify_all();
+        debug!("Batch transcription paused");
+    }
+
+    pub fn resume(&self) {
+        let (lock, cvar) = &*self.queue;
+        lock.lock().unwrap().paused = false;
+        cvar.notify_all();
+        debug!("Batch transcription resumed");
+    }
+
+    /// Cancels every queued file and stops the one being transcribed
+    pub fn cancel(&self) {
+        let (lock, cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        queue.paused = false;
+        for index in 0..queue.files.len() {
+            match queue.files[index].status {
+                BatchFileStatus::Queued => {
+                    queue.files[index].status = BatchFileStatus::Cancelled;
+                    self.emit_progress(&queue, index);
+                }
+                BatchFileStatus::Running => queue.cancel_current = true,
+                _ => {}
+            }
+        }
+        cvar.notify_all();
+        info!("Batch transcription cancelled");
+    }
+
+    pub fn status(&self) -> BatchStatus {
+        let (lock, _) = &*self.queue;
+        let queue = lock.lock().unwrap();
+        BatchStatus {
+            files: queue.files.clone(),
+            running: queue.running,
+            paused: queue.paused,
+        }
+    }
+
+    fn emit_progress(&self, queue: &BatchQueue, index: usize) {
+        let _ = self.app_handle.emit(
+            "batch-transcription-progress",
+            BatchProgressEvent {
+                index,
+                total: queue.files.len(),
+                file: queue.files[index].clone(),
+            },
+        );
+    }
+
+    fn run(&self) {
+        let (lock, cvar) = &*self.queue;
+        loop {
+            let (index, file) = {
+                let mut queue = lock.lock().unwrap();
+                while queue.paused {
+                    queue = cvar.wait(queue).unwrap();
+                }
+                let Some(index) = queue
+                    .files
+                    .iter()
+                    .position(|f| f.status == BatchFileStatus::Queued)
+                else {
+                    queue.running = false;
+                    debug!("Batch transcription queue drained");
+                    return;
+                };
+                queue.cancel_current = false;
+                queue.files[index].status = BatchFileStatus::Running;
+                self.emit_progress(&queue, index);
+                (index, queue.files[index].clone())
+            };
+
+            info!("Batch transcribing {}", file.path);
+            let result = self.transcribe_file(index, &file);
+
+            let mut queue = lock.lock().unwrap();
+            let cancelled = queue.cancel_current;
+            let entry = &mut queue.files[index];
+            match result {
+                Ok(output_path) => {
+                    entry.status = BatchFileStatus::Done;
+                    entry.percent = 100.0;
+                    entry.output_path = output_path.map(|p| p.to_string_lossy().to_string());
+                }
+                Err(_) if cancelled => {
+                    entry.status = BatchFileStatus::Cancelled;
+                }
+                Err(e) => {
+                    error!("Batch transcription of {} failed: {}", file.path, e);
+                    entry.status = BatchFileStatus::Failed;
+                    entry.error = Some(e.to_string());
+                }
+            }
+            self.emit_progress(&queue, index);
+        }
+    }
+
+    /// Transcribes one file and writes its result, returning the sidecar path
+    /// for `txt` and `srt` output.
+    fn transcribe_file(&self, index: usize, file: &BatchFile) -> Result<Option<PathBuf>> {
+        let path = Path::new(&file.path);
+        let samples = decode_audio_file(path)?;
+
+        self.transcription_manager.initiate_model_load();
+        let start = std::time::Instant::now();
+        // Keep the model from being switched or unloaded between segments
+        let output = self.transcription_manager.hold_model(|| {
+            self.transcription_manager
+                .transcribe_long_form(&samples, None, |percent| {
+                    self.on_file_progress(index, percent)
+                })
+        })?;
+
+        match file.output {
+            BatchOutput::Txt => {
+                let (mut out_file, out_path) = create_sidecar(path, "txt")?;
+                out_file.write_all(output.text.as_bytes())?;
+                Ok(Some(out_path))
+            }
+            BatchOutput::Srt => {
+                let (out_file, out_path) = create_sidecar(path, "srt")?;
+                let mut writer = BufWriter::new(out_file);
+                write_segment_subtitles(&mut writer, &output.segments, HistoryExportFormat::Srt)?;
+                writer.flush()?;
+                Ok(Some(out_path))
+            }
+            BatchOutput::History => {
+                let timings = output.timings();
+                let metadata = TranscriptionMetadata {
+                    transcription_ms: Some(start.elapsed().as_millis() as i64),
+                    ..output.metadata
+                };
+                tauri::async_runtime::block_on(self.history_manager.save_transcription(
+                    samples,
+                    output.text,
+                    None,
+                    None,
+                    metadata,
+                    timings,
+                ))?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Records progress for the running file, then blocks while the queue is
+    /// paused. Breaks when the file has been cancelled.
+    fn on_file_progress(&self, index: usize, percent: f32) -> ControlFlow<()> {
+        let (lock, cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        queue.files[index].percent = percent;
+        self.emit_progress(&queue, index);
+
+        while queue.paused && !queue.cancel_current {
+            queue = cvar.wait(queue).unwrap();
+        }
+        if queue.cancel_current {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Creates the file with `extension` next to `audio` for its transcript.
+/// Existing files are never overwritten: when "talk.txt" exists, "talk (1).txt"
+/// is created instead, and so on.
+fn create_sidecar(audio: &Path, extension: &str) -> Result<(fs::File, PathBuf)> {
+    let stem = audio.file_stem().unwrap_or_default().to_string_lossy();
+    let mut attempt = 0;
+    loop {
+        let name = match attempt {
+            0 => format!("{}.{}", stem, extension),
+            n => format!("{} ({}).{}", stem, n, extension),
+        };
+        let candidate = audio.with_file_name(name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((file, candidate)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create {}", candidate.display()))
+            }
+        }
+    }
+}
+
+/// Expands `paths` into the supported audio files they contain. Directories
+/// are searched recursively; files are returned sorted within each directory
+/// and without duplicates.
+fn collect_audio_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_from_dir(path, &mut files)?;
+        } else if path.is_file() {
+            if is_supported_audio_file(path) {
+                files.push(path.clone());
+            } else {
+                warn!("Skipping unsupported file: {}", path.display());
+            }
+        } else {
+            anyhow::bail!("File not found: {}", path.display());
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|f| seen.insert(f.clone()));
+    Ok(files)
+}
+
+fn collect_from_dir(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_from_dir(&path, files)?;
+        } else if is_supported_audio_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_supported_files_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        for name in ["b.wav", "a.MP3", "notes.txt"] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+        fs::write(nested.join("c.flac"), b"").unwrap();
+
+        let files = collect_audio_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("a.MP3"),
+                dir.path().join("b.wav"),
+                nested.join("c.flac"),
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit_files_are_filtered_and_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("talk.ogg");
+        let text = dir.path().join("talk.txt");
+        fs::write(&audio, b"").unwrap();
+        fs::write(&text, b"").unwrap();
+
+        let files = collect_audio_files(&[audio.clone(), text, dir.path().to_path_buf()]).unwrap();
+        assert_eq!(files, vec![audio]);
+    }
+
+    #[test]
+    fn sidecars_never_overwrite_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("talk.ogg");
+        fs::write(dir.path().join("talk.txt"), b"notes").unwrap();
+
+        let (_, first) = create_sidecar(&audio, "txt").unwrap();
+        let (_, second) = create_sidecar(&audio, "txt").unwrap();
+        let (_, subtitles) = create_sidecar(&audio, "srt").unwrap();
+
+        assert_eq!(first, dir.path().join("talk (1).txt"));
+        assert_eq!(second, dir.path().join("talk (2).txt"));
+        assert_eq!(subtitles, dir.path().join("talk.srt"));
+        assert_eq!(fs::read(dir.path().join("talk.txt")).unwrap(), b"notes");
+    }
+
+    #[test]
+    fn missing_paths_are_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(collect_audio_files(&[dir.path().join("missing.wav")]).is_err());
+    }
+}
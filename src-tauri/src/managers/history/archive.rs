@@ -0,0 +1,166 @@
+//! Portable `.tar.gz` archives of the history database and recordings.
+//!
+//! Layout:
+//!
+//! ```text
+//! history.db
+//! recordings/handy-<timestamp>.wav
+//! ```
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::warn;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use tar::{Archive, Builder};
+
+pub const ARCHIVE_DB_NAME: &str = "history.db";
+pub const ARCHIVE_RECORDINGS_DIR: &str = "recordings";
+
+/// A fresh directory under the system temp dir for staging archive contents.
+pub fn staging_dir(prefix: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!(
+        "handy-{}-{}-{}",
+        prefix,
+        std::process::id(),
+        Utc::now().timestamp_millis()
+    ));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Write `db_path` and `recordings` into a gzip-compressed tarball at `out`.
+/// Returns the number of recordings included.
+pub fn write_archive(out: &Path, db_path: &Path, recordings: &[PathBuf]) -> Result<usize> {
+    let encoder = GzEncoder::new(File::create(out)?, Compression::default());
+    let mut builder = Builder::new(encoder);
+
+    builder.append_path_with_name(db_path, ARCHIVE_DB_NAME)?;
+
+    let mut count = 0;
+    for recording in recordings {
+        let Some(name) = recording.file_name() else {
+            continue;
+        };
+        builder.append_path_with_name(recording, Path::new(ARCHIVE_RECORDINGS_DIR).join(name))?;
+        count += 1;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(count)
+}
+
+/// Whether `name` is a single plain path component, so joining it onto a
+/// directory can't point outside of it.
+pub fn is_plain_file_name(name: &str) -> bool {
+    matches!(
+        Path::new(name).components().collect::<Vec<_>>().as_slice(),
+        [Component::Normal(_)]
+    )
+}
+
+/// Whether an archive member is one we know how to import. Anything else
+/// (including absolute paths or `..` components) is ignored.
+fn is_expected_member(path: &Path) -> bool {
+    let components: Vec<Component> = path.components().collect();
+    match components.as_slice() {
+        [Component::Normal(name)] => name.to_str() == Some(ARCHIVE_DB_NAME),
+        [Component::Normal(dir), Component::Normal(_)] => {
+            dir.to_str() == Some(ARCHIVE_RECORDINGS_DIR)
+        }
+        _ => false,
+    }
+}
+
+/// Extract a history archive into `dest`, which must already exist.
+pub fn unpack_archive(archive_path: &Path, dest: &Path) -> Result<()> {
+    let mut archive = Archive::new(GzDecoder::new(File::open(archive_path)?));
+    fs::create_dir_all(dest.join(ARCHIVE_RECORDINGS_DIR))?;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+
+        if !entry.header().entry_type().is_file() || !is_expected_member(&path) {
+            if !entry.header().entry_type().is_dir() {
+                warn!("Skipping unexpected archive entry: {:?}", path);
+            }
+            continue;
+        }
+
+        entry.unpack_in(dest)?;
+    }
+
+    if !dest.join(ARCHIVE_DB_NAME).exists() {
+        return Err(anyhow!(
+            "Archive does not contain {}; is this a Handy history export?",
+            ARCHIVE_DB_NAME
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_round_trip() {
+        let src = tempfile::tempdir().expect("create src dir");
+        let db = src.path().join("snapshot.db");
+        fs::write(&db, b"db").expect("write db");
+        let wav = src.path().join("handy-1.wav");
+        fs::write(&wav, b"wav").expect("write wav");
+
+        let out = src.path().join("history.tar.gz");
+        let count = write_archive(&out, &db, &[wav]).expect("write archive");
+        assert_eq!(count, 1);
+
+        let dest = tempfile::tempdir().expect("create dest dir");
+        unpack_archive(&out, dest.path()).expect("unpack archive");
+        assert_eq!(fs::read(dest.path().join(ARCHIVE_DB_NAME)).unwrap(), b"db");
+        assert_eq!(
+            fs::read(dest.path().join(ARCHIVE_RECORDINGS_DIR).join("handy-1.wav")).unwrap(),
+            b"wav"
+        );
+    }
+
+    #[test]
+    fn rejects_archives_without_database() {
+        let src = tempfile::tempdir().expect("create src dir");
+        let out = src.path().join("empty.tar.gz");
+        let encoder = GzEncoder::new(File::create(&out).unwrap(), Compression::default());
+        Builder::new(encoder)
+            .into_inner()
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        let dest = tempfile::tempdir().expect("create dest dir");
+        assert!(unpack_archive(&out, dest.path()).is_err());
+    }
+
+    #[test]
+    fn only_expected_members_are_accepted() {
+        assert!(is_expected_member(Path::new("history.db")));
+        assert!(is_expected_member(Path::new("recordings/handy-1.wav")));
+        assert!(!is_expected_member(Path::new("../history.db")));
+        assert!(!is_expected_member(Path::new("recordings/../../evil")));
+        assert!(!is_expected_member(Path::new("/etc/passwd")));
+        assert!(!is_expected_member(Path::new("other/file")));
+    }
+
+    #[test]
+    fn only_plain_file_names_are_accepted() {
+        assert!(is_plain_file_name("handy-1.wav"));
+        assert!(!is_plain_file_name(""));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name("../handy-1.wav"));
+        assert!(!is_plain_file_name("recordings/handy-1.wav"));
+        assert!(!is_plain_file_name("/etc/passwd"));
+    }
+}
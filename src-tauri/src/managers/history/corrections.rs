@@ -0,0 +1,180 @@
+//! Turn manual corrections in history into vocabulary suggestions.
+//!
+//! Each edited entry is diffed word-by-word against its raw transcription.
+//! Substitutions that show up repeatedly are offered as custom words when
+//! `apply_custom_words` would reproduce them once the corrected word is in the
+//! vocabulary, and as literal replacement rules otherwise.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::audio_toolkit::{apply_custom_words, apply_replacement_rules, diff_word_substitutions};
+use crate::settings::{ReplacementMatchKind, ReplacementRule};
+
+/// How many times a substitution must be seen before it is suggested
+pub const MIN_CORRECTION_OCCURRENCES: usize = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CorrectionSuggestionKind {
+    /// Add `to` to `custom_words`
+    CustomWord,
+    /// Add a literal rule replacing `from` with `to`
+    ReplacementRule,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct CorrectionSuggestion {
+    /// Stable identifier used to accept or reject the suggestion
+    pub id: String,
+    pub kind: CorrectionSuggestionKind,
+    /// What the model transcribed
+    pub from: String,
+    /// What the user corrected it to
+    pub to: String,
+    pub occurrences: usize,
+    pub entry_ids: Vec<i64>,
+}
+
+impl CorrectionSuggestion {
+    /// The replacement rule accepting this suggestion would add
+    pub fn to_replacement_rule(&self) -> ReplacementRule {
+        ReplacementRule {
+            id: self.id.clone(),
+            pattern: self.from.clone(),
+            replacement: self.to.clone(),
+            match_kind: ReplacementMatchKind::Literal,
+            whole_word: true,
+            case_sensitive: false,
+            enabled: true,
+        }
+    }
+}
+
+pub fn suggestion_id(from: &str, to: &str) -> String {
+    format!("{}=>{}", from.to_lowercase(), to)
+}
+
+/// Build suggestions from `(entry_id, transcription_text, corrected_text)` triples.
+///
+/// Suggestions the user has rejected, and substitutions the current
+/// `custom_words` or replacement rules already produce, are skipped.
+pub fn collect_suggestions(
+    corrections: &[(i64, String, String)],
+    custom_words: &[String],
+    threshold: f64,
+    rules: &[ReplacementRule],
+    rejected: &[String],
+) -> Vec<CorrectionSuggestion> {
+    let mut suggestions: Vec<CorrectionSuggestion> = Vec::new();
+
+    for (entry_id, original, corrected) in corrections {
+        for (from, to) in diff_word_substitutions(original, corrected) {
+            let id = suggestion_id(&from, &to);
+            match suggestions.iter_mut().find(|s| s.id == id) {
+                Some(existing) => {
+                    existing.occurrences += 1;
+                    if !existing.entry_ids.contains(entry_id) {
+                        existing.entry_ids.push(*entry_id);
+                    }
+                }
+                None => suggestions.push(CorrectionSuggestion {
+                    id,
+                    kind: CorrectionSuggestionKind::CustomWord,
+                    from,
+                    to,
+                    occurrences: 1,
+                    entry_ids: vec![*entry_id],
+                }),
+            }
+        }
+    }
+
+    suggestions.retain(|s| {
+        s.occurrences >= MIN_CORRECTION_OCCURRENCES
+            && !rejected.contains(&s.id)
+            && !custom_words.iter().any(|w| w.eq_ignore_ascii_case(&s.to))
+            && !produces(&s.from, &s.to, custom_words, threshold)
+            && !apply_replacement_rules(&s.from, rules).eq_ignore_ascii_case(&s.to)
+    });
+
+    for suggestion in &mut suggestions {
+        if !produces(
+            &suggestion.from,
+            &suggestion.to,
+            std::slice::from_ref(&suggestion.to),
+            threshold,
+        ) {
+            suggestion.kind = CorrectionSuggestionKind::ReplacementRule;
+        }
+    }
+
+    suggestions.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then(a.to.cmp(&b.to)));
+    suggestions
+}
+
+/// Whether `apply_custom_words` turns `from` into `to` with the given vocabulary
+fn produces(from: &str, to: &str, custom_words: &[String], threshold: f64) -> bool {
+    apply_custom_words(from, custom_words, threshold).to_lowercase() == to.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn correction(id: i64, original: &str, corrected: &str) -> (i64, String, String) {
+        (id, original.to_string(), corrected.to_string())
+    }
+
+    #[test]
+    fn suggests_repeated_substitutions() {
+        let corrections = vec![
+            correction(1, "we use charge bee", "we use ChargeBee"),
+            correction(2, "charge bee invoices", "ChargeBee invoices"),
+        ];
+        let suggestions = collect_suggestions(&corrections, &[], 0.18, &[], &[]);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].kind, CorrectionSuggestionKind::CustomWord);
+        assert_eq!(suggestions[0].from, "charge bee");
+        assert_eq!(suggestions[0].to, "ChargeBee");
+        assert_eq!(suggestions[0].occurrences, 2);
+        assert_eq!(suggestions[0].entry_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn ignores_one_off_substitutions() {
+        let corrections = vec![correction(1, "we use charge bee", "we use ChargeBee")];
+        assert!(collect_suggestions(&corrections, &[], 0.18, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn skips_known_and_rejected_words() {
+        let corrections = vec![
+            correction(1, "we use charge bee", "we use ChargeBee"),
+            correction(2, "charge bee invoices", "ChargeBee invoices"),
+        ];
+        let known = vec!["ChargeBee".to_string()];
+        assert!(collect_suggestions(&corrections, &known, 0.18, &[], &[]).is_empty());
+
+        let rejected = vec![suggestion_id("charge bee", "ChargeBee")];
+        assert!(collect_suggestions(&corrections, &[], 0.18, &[], &rejected).is_empty());
+    }
+
+    #[test]
+    fn suggests_replacement_rules_for_rewrites() {
+        let corrections = vec![
+            correction(1, "send it to bob", "send it to Robert"),
+            correction(2, "ask bob", "ask Robert"),
+        ];
+        let suggestions = collect_suggestions(&corrections, &[], 0.18, &[], &[]);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            suggestions[0].kind,
+            CorrectionSuggestionKind::ReplacementRule
+        );
+
+        // Once the rule exists the suggestion goes away
+        let rules = vec![suggestions[0].to_replacement_rule()];
+        assert!(collect_suggestions(&corrections, &[], 0.18, &rules, &[]).is_empty());
+    }
+}
@@ -0,0 +1,450 @@
+//! Export transcription history to portable formats.
+//!
+//! Entries are written oldest first. Subtitle formats (SRT/VTT) lay the
+//! recordings out back-to-back on a single timeline, using the duration of
+//! each entry's WAV file (or an estimate from the word count when the
+//! recording has already been cleaned up). Entries with segment timings get
+//! one cue per segment, unless their text was edited or post-processed.
+
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::{HistoryEntry, TranscriptionSegment};
+
+/// Rough speaking rate used to estimate cue length when no audio is available
+const ESTIMATED_SECONDS_PER_WORD: f64 = 0.4;
+const MIN_CUE_SECONDS: f64 = 1.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryExportFormat {
+    Jsonl,
+    Csv,
+    Markdown,
+    Srt,
+    Vtt,
+}
+
+impl HistoryExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            HistoryExportFormat::Jsonl => "jsonl",
+            HistoryExportFormat::Csv => "csv",
+            HistoryExportFormat::Markdown => "md",
+            HistoryExportFormat::Srt => "srt",
+            HistoryExportFormat::Vtt => "vtt",
+        }
+    }
+}
+
+impl FromStr for HistoryExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jsonl" | "json" => Ok(HistoryExportFormat::Jsonl),
+            "csv" => Ok(HistoryExportFormat::Csv),
+            "markdown" | "md" => Ok(HistoryExportFormat::Markdown),
+            "srt" => Ok(HistoryExportFormat::Srt),
+            "vtt" => Ok(HistoryExportFormat::Vtt),
+            other => Err(format!(
+                "Invalid export format '{}'. Supported: jsonl, csv, markdown, srt, vtt",
+                other
+            )),
+        }
+    }
+}
+
+/// Read the duration of a WAV recording in seconds.
+pub fn wav_duration_secs(path: &Path) -> Option<f64> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let spec = reader.spec();
+    if spec.sample_rate == 0 {
+        return None;
+    }
+    Some(reader.duration() as f64 / spec.sample_rate as f64)
+}
+
+fn estimate_duration_secs(text: &str) -> f64 {
+    (text.split_whitespace().count() as f64 * ESTIMATED_SECONDS_PER_WORD).max(MIN_CUE_SECONDS)
+}
+
+fn local_datetime(timestamp: i64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp(timestamp, 0).map(|utc| utc.with_timezone(&Local))
+}
+
+/// Write `entries` to `writer` in the requested format.
+///
+/// `duration_of` returns the audio duration for an entry, if known. It is only
+/// consulted for subtitle formats.
+pub fn write_export<W: Write>(
+    writer: &mut W,
+    entries: &[HistoryEntry],
+    format: HistoryExportFormat,
+    duration_of: &dyn Fn(&HistoryEntry) -> Option<f64>,
+) -> Result<()> {
+    let mut sorted: Vec<&HistoryEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| (e.timestamp, e.id));
+
+    match format {
+        HistoryExportFormat::Jsonl => write_jsonl(writer, &sorted),
+        HistoryExportFormat::Csv => write_csv(writer, &sorted),
+        HistoryExportFormat::Markdown => write_markdown(writer, &sorted),
+        HistoryExportFormat::Srt | HistoryExportFormat::Vtt => {
+            write_subtitles(writer, &sorted, format, duration_of)
+        }
+    }
+}
+
+fn write_jsonl<W: Write>(writer: &mut W, entries: &[&HistoryEntry]) -> Result<()> {
+    for entry in entries {
+        serde_json::to_writer(&mut *writer, entry)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn optional_number(value: Option<i64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn write_csv<W: Write>(writer: &mut W, entries: &[&HistoryEntry]) -> Result<()> {
+    writeln!(
+        writer,
+        "id,timestamp,datetime,saved,title,transcription_text,post_processed_text,post_process_prompt,file_name,\
+         model_id,engine_type,language,audio_duration_ms,transcription_ms,post_process_provider,post_process_model"
+    )?;
+    for entry in entries {
+        let datetime = local_datetime(entry.timestamp)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default();
+        let fields = [
+            entry.id.to_string(),
+            entry.timestamp.to_string(),
+            datetime,
+            entry.saved.to_string(),
+            csv_field(&entry.title),
+            csv_field(&entry.transcription_text),
+            csv_field(entry.post_processed_text.as_deref().unwrap_or("")),
+            csv_field(entry.post_process_prompt.as_deref().unwrap_or("")),
+            csv_field(&entry.file_name),
+            csv_field(entry.metadata.model_id.as_deref().unwrap_or("")),
+            csv_field(entry.metadata.engine_type.as_deref().unwrap_or("")),
+            csv_field(entry.metadata.language.as_deref().unwrap_or("")),
+            optional_number(entry.metadata.audio_duration_ms),
+            optional_number(entry.metadata.transcription_ms),
+            csv_field(
+                entry
+                    .metadata
+                    .post_process_provider
+                    .as_deref()
+                    .unwrap_or(""),
+            ),
+            csv_field(entry.metadata.post_process_model.as_deref().unwrap_or("")),
+        ];
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+fn write_markdown<W: Write>(writer: &mut W, entries: &[&HistoryEntry]) -> Result<()> {
+    writeln!(writer, "# Handy Transcription History")?;
+
+    // Outer None means no heading has been written yet
+    let mut current_day: Option<Option<NaiveDate>> = None;
+    for entry in entries {
+        let datetime = local_datetime(entry.timestamp);
+        let day = datetime.map(|dt| dt.date_naive());
+
+        if current_day != Some(day) {
+            writeln!(writer)?;
+            match day {
+                Some(day) => writeln!(writer, "## {}", day.format("%A, %B %-d, %Y"))?,
+                None => writeln!(writer, "## Unknown date")?,
+            }
+            current_day = Some(day);
+        }
+
+        let time = datetime
+            .map(|dt| dt.format("%-I:%M %p").to_string())
+            .unwrap_or_else(|| entry.title.clone());
+        let marker = if entry.saved { " ★" } else { "" };
+
+        writeln!(writer)?;
+        writeln!(writer, "### {}{}", time, marker)?;
+        writeln!(writer)?;
+        writeln!(writer, "{}", entry.final_text().trim())?;
+    }
+    Ok(())
+}
+
+fn format_subtitle_time(seconds: f64, format: HistoryExportFormat) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_ms / 3_600_000;
+    let minutes = (total_ms / 60_000) % 60;
+    let secs = (total_ms / 1000) % 60;
+    let ms = total_ms % 1000;
+    let separator = if format == HistoryExportFormat::Srt {
+        ','
+    } else {
+        '.'
+    };
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        hours, minutes, secs, separator, ms
+    )
+}
+
+fn write_subtitle_header<W: Write>(writer: &mut W, format: HistoryExportFormat) -> Result<()> {
+    if format == HistoryExportFormat::Vtt {
+        writeln!(writer, "WEBVTT")?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn write_cue<W: Write>(
+    writer: &mut W,
+    number: usize,
+    start: f64,
+    end: f64,
+    text: &str,
+    format: HistoryExportFormat,
+) -> Result<()> {
+    if format == HistoryExportFormat::Srt {
+        writeln!(writer, "{}", number)?;
+    }
+    writeln!(
+        writer,
+        "{} --> {}",
+        format_subtitle_time(start, format),
+        format_subtitle_time(end, format)
+    )?;
+    writeln!(writer, "{}", text)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Write the segments of a single transcription as SRT or VTT cues.
+pub fn write_segment_subtitles<W: Write>(
+    writer: &mut W,
+    segments: &[TranscriptionSegment],
+    format: HistoryExportFormat,
+) -> Result<()> {
+    write_subtitle_header(writer, format)?;
+    for (index, segment) in segments.iter().enumerate() {
+        write_cue(
+            writer,
+            index + 1,
+            segment.start_ms as f64 / 1000.0,
+            segment.end_ms as f64 / 1000.0,
+            segment.text.trim(),
+            format,
+        )?;
+    }
+    Ok(())
+}
+
+fn write_subtitles<W: Write>(
+    writer: &mut W,
+    entries: &[&HistoryEntry],
+    format: HistoryExportFormat,
+    duration_of: &dyn Fn(&HistoryEntry) -> Option<f64>,
+) -> Result<()> {
+    write_subtitle_header(writer, format)?;
+
+    let mut cursor = 0.0;
+    let mut cue_number = 0;
+    for entry in entries {
+        let text = entry.final_text().trim();
+        let duration = duration_of(entry)
+            .filter(|d| *d > 0.0)
+            .unwrap_or_else(|| estimate_duration_secs(text));
+
+        // Segment texts describe the raw transcription only
+        let segments = entry
+            .timings
+            .as_ref()
+            .filter(|_| entry.final_text() == entry.transcription_text)
+            .map(|t| t.segments.as_slice())
+            .unwrap_or_default();
+
+        let mut cues: Vec<(f64, f64, &str)> = segments
+            .iter()
+            .map(|s| {
+                (
+                    s.start_ms as f64 / 1000.0,
+                    s.end_ms as f64 / 1000.0,
+                    s.text.trim(),
+                )
+            })
+            .collect();
+        if cues.is_empty() {
+            cues.push((0.0, duration, text));
+        }
+
+        for (start, end, cue_text) in &cues {
+            cue_number += 1;
+            write_cue(
+                writer,
+                cue_number,
+                cursor + start,
+                cursor + end,
+                cue_text,
+                format,
+            )?;
+        }
+
+        let last_end = cues.iter().map(|(_, end, _)| *end).fold(0.0, f64::max);
+        cursor += duration.max(last_end);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::history::TranscriptionTimings;
+
+    fn build_entry(
+        id: i64,
+        timestamp: i64,
+        text: &str,
+        post_processed: Option<&str>,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            id,
+            file_name: format!("handy-{}.wav", timestamp),
+            timestamp,
+            saved: false,
+            title: format!("Recording {}", timestamp),
+            transcription_text: text.to_string(),
+            post_processed_text: post_processed.map(|t| t.to_string()),
+            post_process_prompt: None,
+            metadata: Default::default(),
+            edited_text: None,
+            timings: None,
+        }
+    }
+
+    fn export_to_string(
+        entries: &[HistoryEntry],
+        format: HistoryExportFormat,
+        duration_of: &dyn Fn(&HistoryEntry) -> Option<f64>,
+    ) -> String {
+        let mut out = Vec::new();
+        write_export(&mut out, entries, format, duration_of).expect("export history");
+        String::from_utf8(out).expect("utf8 output")
+    }
+
+    #[test]
+    fn parses_export_formats() {
+        assert_eq!(
+            "JSONL".parse::<HistoryExportFormat>(),
+            Ok(HistoryExportFormat::Jsonl)
+        );
+        assert_eq!(
+            "md".parse::<HistoryExportFormat>(),
+            Ok(HistoryExportFormat::Markdown)
+        );
+        assert!("docx".parse::<HistoryExportFormat>().is_err());
+    }
+
+    #[test]
+    fn jsonl_writes_one_entry_per_line_oldest_first() {
+        let entries = vec![
+            build_entry(2, 200, "second", None),
+            build_entry(1, 100, "first", None),
+        ];
+        let out = export_to_string(&entries, HistoryExportFormat::Jsonl, &|_| None);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: HistoryEntry = serde_json::from_str(lines[0]).expect("parse json line");
+        assert_eq!(first.transcription_text, "first");
+    }
+
+    #[test]
+    fn csv_escapes_special_characters() {
+        let entries = vec![build_entry(1, 100, "hello, \"world\"\nagain", None)];
+        let out = export_to_string(&entries, HistoryExportFormat::Csv, &|_| None);
+        assert!(out.starts_with("id,timestamp,datetime,"));
+        assert!(out.contains("\"hello, \"\"world\"\"\nagain\""));
+    }
+
+    #[test]
+    fn markdown_groups_entries_by_day() {
+        let day = 24 * 60 * 60;
+        let entries = vec![
+            build_entry(1, 10 * day + 3600 * 12, "morning", None),
+            build_entry(2, 10 * day + 3600 * 12 + 60, "raw", Some("processed")),
+            build_entry(3, 12 * day + 3600 * 12, "later", None),
+        ];
+        let out = export_to_string(&entries, HistoryExportFormat::Markdown, &|_| None);
+        assert_eq!(out.matches("\n## ").count(), 2);
+        assert_eq!(out.matches("\n### ").count(), 3);
+        assert!(out.contains("processed"));
+        assert!(!out.contains("raw"));
+    }
+
+    #[test]
+    fn srt_uses_audio_durations_for_cue_timing() {
+        let entries = vec![
+            build_entry(1, 100, "first cue", None),
+            build_entry(2, 200, "second cue", None),
+        ];
+        let out = export_to_string(&entries, HistoryExportFormat::Srt, &|e| {
+            Some(if e.id == 1 { 2.5 } else { 61.25 })
+        });
+        assert!(out.contains("1\n00:00:00,000 --> 00:00:02,500\nfirst cue\n"));
+        assert!(out.contains("2\n00:00:02,500 --> 00:01:03,750\nsecond cue\n"));
+    }
+
+    #[test]
+    fn srt_uses_segment_timings_when_available() {
+        let mut timed = build_entry(1, 100, "hello there. general kenobi", None);
+        timed.timings = Some(TranscriptionTimings {
+            segments: vec![
+                TranscriptionSegment {
+                    start_ms: 500,
+                    end_ms: 1500,
+                    text: "hello there.".to_string(),
+                },
+                TranscriptionSegment {
+                    start_ms: 2000,
+                    end_ms: 3000,
+                    text: "general kenobi".to_string(),
+                },
+            ],
+            words: None,
+        });
+        let mut edited = build_entry(2, 200, "raw", None);
+        edited.edited_text = Some("edited".to_string());
+        edited.timings = timed.timings.clone();
+
+        let out = export_to_string(&[timed, edited], HistoryExportFormat::Srt, &|_| Some(4.0));
+        assert!(out.contains("1\n00:00:00,500 --> 00:00:01,500\nhello there.\n"));
+        assert!(out.contains("2\n00:00:02,000 --> 00:00:03,000\ngeneral kenobi\n"));
+        assert!(out.contains("3\n00:00:04,000 --> 00:00:08,000\nedited\n"));
+    }
+
+    #[test]
+    fn vtt_estimates_duration_without_audio() {
+        let entries = vec![build_entry(1, 100, "one two three four five", None)];
+        let out = export_to_string(&entries, HistoryExportFormat::Vtt, &|_| None);
+        assert!(out.starts_with("WEBVTT\n\n"));
+        assert!(out.contains("00:00:00.000 --> 00:00:02.000"));
+    }
+}
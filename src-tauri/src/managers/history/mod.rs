@@ -0,0 +1,2050 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use log::{debug, error, info, warn};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use rusqlite_migration::{Migrations, M};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::save_wav_file;
+
+pub mod archive;
+pub mod corrections;
+pub mod export;
+pub mod timings;
+
+use archive::{
+    is_plain_file_name, staging_dir, unpack_archive, write_archive, ARCHIVE_DB_NAME,
+    ARCHIVE_RECORDINGS_DIR,
+};
+use corrections::{collect_suggestions, CorrectionSuggestion};
+use export::{wav_duration_secs, write_export, HistoryExportFormat};
+pub use timings::{TranscriptionOutput, TranscriptionSegment, TranscriptionTimings};
+
+/// Database file and recordings directory, inside the app data directory
+const DB_FILE_NAME: &str = "history.db";
+const RECORDINGS_DIR_NAME: &str = "recordings";
+
+/// Database migrations for transcription history.
+/// Each migration is applied in order. The library tracks which migrations
+/// have been applied using SQLite's user_version pragma.
+///
+/// Note: For users upgrading from tauri-plugin-sql, migrate_from_tauri_plugin_sql()
+/// converts the old _sqlx_migrations table tracking to the user_version pragma,
+/// ensuring migrations don't re-run on existing databases.
+static MIGRATIONS: &[M] = &[
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_name TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            saved BOOLEAN NOT NULL DEFAULT 0,
+            title TEXT NOT NULL,
+            transcription_text TEXT NOT NULL
+        );",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    // Full-text index over the raw and post-processed text. The FTS table uses
+    // transcription_history as external content and is kept in sync by triggers.
+    M::up(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS transcription_history_fts USING fts5(
+            transcription_text,
+            post_processed_text,
+            content='transcription_history',
+            content_rowid='id'
+        );
+        INSERT INTO transcription_history_fts(transcription_history_fts) VALUES('rebuild');
+        CREATE TRIGGER IF NOT EXISTS transcription_history_fts_insert
+        AFTER INSERT ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text)
+            VALUES (new.id, new.transcription_text, new.post_processed_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transcription_history_fts_delete
+        AFTER DELETE ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(transcription_history_fts, rowid, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.transcription_text, old.post_processed_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transcription_history_fts_update
+        AFTER UPDATE OF transcription_text, post_processed_text ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(transcription_history_fts, rowid, transcription_text, post_processed_text)
+            VALUES ('delete', old.id, old.transcription_text, old.post_processed_text);
+            INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text)
+            VALUES (new.id, new.transcription_text, new.post_processed_text);
+        END;",
+    ),
+    M::up(
+        "CREATE INDEX IF NOT EXISTS idx_transcription_history_timestamp_id
+            ON transcription_history(timestamp DESC, id DESC);
+        CREATE INDEX IF NOT EXISTS idx_transcription_history_saved
+            ON transcription_history(saved, timestamp DESC, id DESC);
+        CREATE INDEX IF NOT EXISTS idx_transcription_history_post_processed
+            ON transcription_history(timestamp DESC, id DESC)
+            WHERE post_processed_text IS NOT NULL;",
+    ),
+    // Per-entry metadata about how the transcription was produced
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN model_id TEXT;
+        ALTER TABLE transcription_history ADD COLUMN engine_type TEXT;
+        ALTER TABLE transcription_history ADD COLUMN language TEXT;
+        ALTER TABLE transcription_history ADD COLUMN audio_duration_ms INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN transcription_ms INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN post_process_provider TEXT;
+        ALTER TABLE transcription_history ADD COLUMN post_process_model TEXT;
+        ALTER TABLE transcription_history ADD COLUMN post_process_ms INTEGER;
+        ALTER TABLE transcription_history ADD COLUMN binding_id TEXT;",
+    ),
+    // Alternate versions of an entry's text. The original row is never rewritten;
+    // revisions are removed together with their entry.
+    M::up(
+        "CREATE TABLE IF NOT EXISTS history_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_id INTEGER NOT NULL REFERENCES transcription_history(id) ON DELETE CASCADE,
+            kind TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            transcription_text TEXT NOT NULL,
+            post_processed_text TEXT,
+            post_process_prompt TEXT,
+            metadata TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_history_revisions_entry
+            ON history_revisions(entry_id, created_at, id);
+        CREATE TRIGGER IF NOT EXISTS history_revisions_delete_with_entry
+        AFTER DELETE ON transcription_history BEGIN
+            DELETE FROM history_revisions WHERE entry_id = old.id;
+        END;",
+    ),
+    // Manual edits are revisions too. The view exposes the latest edit next to
+    // each entry so every entry query can read it without joining by hand.
+    M::up(
+        "ALTER TABLE history_revisions ADD COLUMN original_text TEXT;
+        CREATE VIEW IF NOT EXISTS transcription_history_with_edits AS
+        SELECT h.*,
+            (SELECT r.transcription_text FROM history_revisions r
+             WHERE r.entry_id = h.id AND r.kind = 'edit'
+             ORDER BY r.created_at DESC, r.id DESC LIMIT 1) AS edited_text
+        FROM transcription_history h;",
+    ),
+    // Segment and word timings as JSON, for engines that report them
+    M::up("ALTER TABLE transcription_history ADD COLUMN timings TEXT;"),
+    // Index the latest manual edit too, so entries can be found by the text
+    // shown for them. The edit lives in another table, so the index keeps its
+    // own copy of the text instead of reading transcription_history.
+    M::up(
+        "DROP TRIGGER IF EXISTS transcription_history_fts_insert;
+        DROP TRIGGER IF EXISTS transcription_history_fts_delete;
+        DROP TRIGGER IF EXISTS transcription_history_fts_update;
+        DROP TABLE IF EXISTS transcription_history_fts;
+        CREATE VIRTUAL TABLE transcription_history_fts USING fts5(
+            transcription_text,
+            post_processed_text,
+            edited_text
+        );
+        INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text, edited_text)
+            SELECT id, transcription_text, post_processed_text, edited_text
+            FROM transcription_history_with_edits;
+        CREATE TRIGGER transcription_history_fts_insert
+        AFTER INSERT ON transcription_history BEGIN
+            INSERT INTO transcription_history_fts(rowid, transcription_text, post_processed_text)
+            VALUES (new.id, new.transcription_text, new.post_processed_text);
+        END;
+        CREATE TRIGGER transcription_history_fts_delete
+        AFTER DELETE ON transcription_history BEGIN
+            DELETE FROM transcription_history_fts WHERE rowid = old.id;
+        END;
+        CREATE TRIGGER transcription_history_fts_update
+        AFTER UPDATE OF transcription_text, post_processed_text ON transcription_history BEGIN
+            UPDATE transcription_history_fts
+            SET transcription_text = new.transcription_text,
+                post_processed_text = new.post_processed_text
+            WHERE rowid = new.id;
+        END;
+        CREATE TRIGGER history_revisions_fts_insert
+        AFTER INSERT ON history_revisions WHEN new.kind = 'edit' BEGIN
+            UPDATE transcription_history_fts
+            SET edited_text = (SELECT edited_text FROM transcription_history_with_edits WHERE id = new.entry_id)
+            WHERE rowid = new.entry_id;
+        END;
+        CREATE TRIGGER history_revisions_fts_delete
+        AFTER DELETE ON history_revisions WHEN old.kind = 'edit' BEGIN
+            UPDATE transcription_history_fts
+            SET edited_text = (SELECT edited_text FROM transcription_history_with_edits WHERE id = old.entry_id)
+            WHERE rowid = old.entry_id;
+        END;",
+    ),
+];
+
+/// Default and maximum page sizes for `HistoryManager::query_history`.
+const DEFAULT_HISTORY_PAGE_SIZE: usize = 50;
+const MAX_HISTORY_PAGE_SIZE: usize = 500;
+
+const HISTORY_REVISION_COLUMNS: &str = "id, entry_id, kind, created_at, transcription_text, post_processed_text, post_process_prompt, metadata, original_text";
+
+/// Columns selected for every `HistoryEntry` query. Read them from
+/// `transcription_history_with_edits`, which adds `edited_text`.
+const HISTORY_ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, \
+     model_id, engine_type, language, audio_duration_ms, transcription_ms, post_process_provider, post_process_model, post_process_ms, binding_id, edited_text, timings";
+
+/// How a transcription was produced. Every field is optional because entries
+/// recorded before this metadata existed have none of it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptionMetadata {
+    pub model_id: Option<String>,
+    pub engine_type: Option<String>,
+    /// Language passed to the engine ("auto" when auto-detecting)
+    pub language: Option<String>,
+    pub audio_duration_ms: Option<i64>,
+    /// Time spent in the speech-to-text engine
+    pub transcription_ms: Option<i64>,
+    pub post_process_provider: Option<String>,
+    pub post_process_model: Option<String>,
+    /// Time spent waiting on the post-processing LLM
+    pub post_process_ms: Option<i64>,
+    /// Shortcut binding that triggered the recording, if any
+    pub binding_id: Option<String>,
+}
+
+/// A new row for `transcription_history`, written by `save_to_database`
+struct NewHistoryEntry {
+    file_name: String,
+    timestamp: i64,
+    title: String,
+    transcription_text: String,
+    post_processed_text: Option<String>,
+    post_process_prompt: Option<String>,
+    metadata: TranscriptionMetadata,
+    timings: Option<TranscriptionTimings>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryRevisionKind {
+    /// The recording was run through a model again
+    Retranscription,
+    /// The user corrected the text by hand
+    Edit,
+}
+
+impl HistoryRevisionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HistoryRevisionKind::Retranscription => "retranscription",
+            HistoryRevisionKind::Edit => "edit",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "retranscription" => Some(HistoryRevisionKind::Retranscription),
+            "edit" => Some(HistoryRevisionKind::Edit),
+            _ => None,
+        }
+    }
+}
+
+/// An alternate version of a history entry's text, linked to the original.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistoryRevision {
+    pub id: i64,
+    pub entry_id: i64,
+    pub kind: HistoryRevisionKind,
+    pub created_at: i64,
+    pub transcription_text: String,
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    pub metadata: TranscriptionMetadata,
+    /// For edits, the text that was replaced
+    pub original_text: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub file_name: String,
+    pub timestamp: i64,
+    pub saved: bool,
+    pub title: String,
+    pub transcription_text: String,
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    pub metadata: TranscriptionMetadata,
+    /// Latest manual edit, if the user has corrected this entry
+    pub edited_text: Option<String>,
+    /// Where each segment of the raw transcription sits in the recording
+    pub timings: Option<TranscriptionTimings>,
+}
+
+impl HistoryEntry {
+    /// The text a user would consider "the" transcript: their latest edit,
+    /// then the post-processed text, then the raw transcription.
+    pub fn final_text(&self) -> &str {
+        self.edited_text
+            .as_deref()
+            .or(self.post_processed_text.as_deref())
+            .unwrap_or(&self.transcription_text)
+    }
+}
+
+/// A history entry matched by a full-text search, with a highlighted snippet
+/// of the best matching column. Lower `rank` values are better matches.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistorySearchResult {
+    pub entry: HistoryEntry,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// Position in the history list, ordered by `(timestamp, id)` descending.
+/// The next page starts strictly after this entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HistoryCursor {
+    pub timestamp: i64,
+    pub id: i64,
+}
+
+/// Filters and pagination for `HistoryManager::query_history`.
+/// All filters are optional and combined with AND.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct HistoryQuery {
+    /// Page size, defaults to 50 and is capped at 500
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Cursor returned as `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<HistoryCursor>,
+    /// Inclusive lower bound, unix seconds
+    #[serde(default)]
+    pub from_timestamp: Option<i64>,
+    /// Inclusive upper bound, unix seconds
+    #[serde(default)]
+    pub to_timestamp: Option<i64>,
+    #[serde(default)]
+    pub saved_only: bool,
+    #[serde(default)]
+    pub post_processed_only: bool,
+    /// Case-insensitive substring match on raw or post-processed text
+    #[serde(default)]
+    pub text_contains: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// `None` when there are no more entries
+    pub next_cursor: Option<HistoryCursor>,
+}
+
+/// Escape `%`, `_` and the escape character itself for use in a LIKE pattern.
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Creates an empty recording file in `dir` for audio captured at `now` and
+/// returns its name. Names have millisecond resolution, and a counter is
+/// added when two recordings are saved in the same millisecond.
+fn reserve_recording_file(dir: &Path, now: DateTime<Utc>) -> std::io::Result<String> {
+    let millis = now.timestamp_millis();
+    let mut attempt = 0;
+    loop {
+        let name = match attempt {
+            0 => format!("handy-{}.wav", millis),
+            n => format!("handy-{}-{}.wav", millis, n),
+        };
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(&name))
+        {
+            Ok(_) => return Ok(name),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn map_history_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    // Timings are stored as JSON; tolerate rows written by other versions
+    let timings = row
+        .get::<_, Option<String>>("timings")?
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    Ok(HistoryEntry {
+        id: row.get("id")?,
+        file_name: row.get("file_name")?,
+        timestamp: row.get("timestamp")?,
+        saved: row.get("saved")?,
+        title: row.get("title")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        metadata: TranscriptionMetadata {
+            model_id: row.get("model_id")?,
+            engine_type: row.get("engine_type")?,
+            language: row.get("language")?,
+            audio_duration_ms: row.get("audio_duration_ms")?,
+            transcription_ms: row.get("transcription_ms")?,
+            post_process_provider: row.get("post_process_provider")?,
+            post_process_model: row.get("post_process_model")?,
+            post_process_ms: row.get("post_process_ms")?,
+            binding_id: row.get("binding_id")?,
+        },
+        edited_text: row.get("edited_text")?,
+        timings,
+    })
+}
+
+fn map_history_revision(row: &rusqlite::Row) -> rusqlite::Result<HistoryRevision> {
+    let kind: String = row.get("kind")?;
+    let kind = HistoryRevisionKind::parse(&kind).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            2,
+            rusqlite::types::Type::Text,
+            format!("unknown revision kind '{}'", kind).into(),
+        )
+    })?;
+    // Metadata is stored as JSON; tolerate rows written by other versions
+    let metadata = row
+        .get::<_, Option<String>>("metadata")?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    Ok(HistoryRevision {
+        id: row.get("id")?,
+        entry_id: row.get("entry_id")?,
+        kind,
+        created_at: row.get("created_at")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        metadata,
+        original_text: row.get("original_text")?,
+    })
+}
+
+/// Convert free-form user input into an FTS5 MATCH expression.
+///
+/// Each whitespace-separated term is quoted so FTS5 operators and punctuation in
+/// the input can't produce syntax errors, and the last term is matched as a prefix
+/// so results update while the user is still typing.
+fn build_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    let mut expression = terms.join(" ");
+    expression.push('*');
+    Some(expression)
+}
+
+pub struct HistoryManager {
+    app_handle: AppHandle,
+    recordings_dir: PathBuf,
+    db_path: PathBuf,
+}
+
+impl HistoryManager {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        // Create recordings directory in app data dir
+        let app_data_dir = app_handle.path().app_data_dir()?;
+        let recordings_dir = app_data_dir.join(RECORDINGS_DIR_NAME);
+        let db_path = app_data_dir.join(DB_FILE_NAME);
+
+        // Ensure recordings directory exists
+        if !recordings_dir.exists() {
+            fs::create_dir_all(&recordings_dir)?;
+            debug!("Created recordings directory: {:?}", recordings_dir);
+        }
+
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            recordings_dir,
+            db_path,
+        };
+
+        // Initialize database and run migrations synchronously
+        Self::init_database(&manager.db_path)?;
+
+        Ok(manager)
+    }
+
+    fn init_database(db_path: &Path) -> Result<()> {
+        info!("Initializing database at {:?}", db_path);
+
+        let mut conn = Connection::open(db_path)?;
+
+        // Handle migration from tauri-plugin-sql to rusqlite_migration
+        // tauri-plugin-sql used _sqlx_migrations table, rusqlite_migration uses user_version pragma
+        Self::migrate_from_tauri_plugin_sql(&conn)?;
+
+        // Create migrations object and run to latest version
+        let migrations = Migrations::new(MIGRATIONS.to_vec());
+
+        // Validate migrations in debug builds
+        #[cfg(debug_assertions)]
+        migrations.validate().expect("Invalid migrations");
+
+        // Get current version before migration
+        let version_before: i32 =
+            conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        debug!("Database version before migration: {}", version_before);
+
+        // Apply any pending migrations
+        migrations.to_latest(&mut conn)?;
+
+        // Get version after migration
+        let version_after: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        if version_after > version_before {
+            info!(
+                "Database migrated from version {} to {}",
+                version_before, version_after
+            );
+        } else {
+            debug!("Database already at latest version {}", version_after);
+        }
+
+        Ok(())
+    }
+
+    /// Migrate from tauri-plugin-sql's migration tracking to rusqlite_migration's.
+    /// tauri-plugin-sql used a _sqlx_migrations table, while rusqlite_migration uses
+    /// SQLite's user_version pragma. This function checks if the old system was in use
+    /// and sets the user_version accordingly so migrations don't re-run.
+    fn migrate_from_tauri_plugin_sql(conn: &Connection) -> Result<()> {
+        // Check if the old _sqlx_migrations table exists
+        let has_sqlx_migrations: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='_sqlx_migrations'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if !has_sqlx_migrations {
+            return Ok(());
+        }
+
+        // Check current user_version
+        let current_version: i32 =
+            conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        if current_version > 0 {
+            // Already migrated to rusqlite_migration system
+            return Ok(());
+        }
+
+        // Get the highest version from the old migrations table
+        let old_version: i32 = conn
+            .query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+
+        if old_version > 0 {
+            info!(
+                "Migrating from tauri-plugin-sql (version {}) to rusqlite_migration",
+                old_version
+            );
+
+            // Set user_version to match the old migration state
+            conn.pragma_update(None, "user_version", old_version)?;
+
+            // Optionally drop the old migrations table (keeping it doesn't hurt)
+            // conn.execute("DROP TABLE IF EXISTS _sqlx_migrations", [])?;
+
+            info!(
+                "Migration tracking converted: user_version set to {}",
+                old_version
+            );
+        }
+
+        Ok(())
+    }
+
+    fn get_connection(&self) -> Result<Connection> {
+        Ok(Connection::open(&self.db_path)?)
+    }
+
+    /// Save a transcription to history (both database and WAV file)
+    pub async fn save_transcription(
+        &self,
+        audio_samples: Vec<f32>,
+        transcription_text: String,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+        mut metadata: TranscriptionMetadata,
+        timings: Option<TranscriptionTimings>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let timestamp = now.timestamp();
+        if metadata.audio_duration_ms.is_none() {
+            metadata.audio_duration_ms =
+                Some(audio_samples.len() as i64 * 1000 / WHISPER_SAMPLE_RATE as i64);
+        }
+        let file_name = reserve_recording_file(&self.recordings_dir, now)?;
+        let title = self.format_timestamp_title(timestamp);
+
+        // Save WAV file
+        let file_path = self.recordings_dir.join(&file_name);
+        if let Err(e) = save_wav_file(&file_path, &audio_samples).await {
+            let _ = fs::remove_file(&file_path);
+            return Err(e);
+        }
+
+        // Save to database
+        self.save_to_database(NewHistoryEntry {
+            file_name,
+            timestamp,
+            title,
+            transcription_text,
+            post_processed_text,
+            post_process_prompt,
+            metadata,
+            timings,
+        })?;
+
+        // Clean up old entries
+        self.cleanup_old_entries()?;
+
+        // Emit history updated event
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    fn save_to_database(&self, entry: NewHistoryEntry) -> Result<()> {
+        let conn = self.get_connection()?;
+        let NewHistoryEntry {
+            file_name,
+            timestamp,
+            title,
+            transcription_text,
+            post_processed_text,
+            post_process_prompt,
+            metadata,
+            timings,
+        } = entry;
+        let timings = timings.as_ref().map(serde_json::to_string).transpose()?;
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, \
+             model_id, engine_type, language, audio_duration_ms, transcription_ms, post_process_provider, post_process_model, post_process_ms, binding_id, timings) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            params![
+                file_name,
+                timestamp,
+                false,
+                title,
+                transcription_text,
+                post_processed_text,
+                post_process_prompt,
+                metadata.model_id,
+                metadata.engine_type,
+                metadata.language,
+                metadata.audio_duration_ms,
+                metadata.transcription_ms,
+                metadata.post_process_provider,
+                metadata.post_process_model,
+                metadata.post_process_ms,
+                metadata.binding_id,
+                timings
+            ],
+        )?;
+
+        debug!("Saved transcription to database");
+        Ok(())
+    }
+
+    pub fn cleanup_old_entries(&self) -> Result<()> {
+        let retention_period = crate::settings::get_recording_retention_period(&self.app_handle);
+
+        match retention_period {
+            crate::settings::RecordingRetentionPeriod::Never => {
+                // Don't delete anything
+                return Ok(());
+            }
+            crate::settings::RecordingRetentionPeriod::PreserveLimit => {
+                // Use the old count-based logic with history_limit
+                let limit = crate::settings::get_history_limit(&self.app_handle);
+                return self.cleanup_by_count(limit);
+            }
+            _ => {
+                // Use time-based logic
+                return self.cleanup_by_time(retention_period);
+            }
+        }
+    }
+
+    fn delete_entries_and_files(&self, entries: &[(i64, String)]) -> Result<usize> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let conn = self.get_connection()?;
+        let mut deleted_count = 0;
+
+        for (id, file_name) in entries {
+            // Delete database entry
+            conn.execute(
+                "DELETE FROM transcription_history WHERE id = ?1",
+                params![id],
+            )?;
+
+            // Delete WAV file
+            let file_path = self.recordings_dir.join(file_name);
+            if file_path.exists() {
+                if let Err(e) = fs::remove_file(&file_path) {
+                    error!("Failed to delete WAV file {}: {}", file_name, e);
+                } else {
+                    debug!("Deleted old WAV file: {}", file_name);
+                    deleted_count += 1;
+                }
+            }
+        }
+
+        Ok(deleted_count)
+    }
+
+    fn cleanup_by_count(&self, limit: usize) -> Result<()> {
+        let conn = self.get_connection()?;
+
+        // Get all entries that are not saved, ordered by timestamp desc
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name FROM transcription_history WHERE saved = 0 ORDER BY timestamp DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
+        })?;
+
+        let mut entries: Vec<(i64, String)> = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        if entries.len() > limit {
+            let entries_to_delete = &entries[limit..];
+            let deleted_count = self.delete_entries_and_files(entries_to_delete)?;
+
+            if deleted_count > 0 {
+                debug!("Cleaned up {} old history entries by count", deleted_count);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cleanup_by_time(
+        &self,
+        retention_period: crate::settings::RecordingRetentionPeriod,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+
+        // Calculate cutoff timestamp (current time minus retention period)
+        let now = Utc::now().timestamp();
+        let cutoff_timestamp = match retention_period {
+            crate::settings::RecordingRetentionPeriod::Days3 => now - (3 * 24 * 60 * 60), // 3 days in seconds
+            crate::settings::RecordingRetentionPeriod::Weeks2 => now - (2 * 7 * 24 * 60 * 60), // 2 weeks in seconds
+            crate::settings::RecordingRetentionPeriod::Months3 => now - (3 * 30 * 24 * 60 * 60), // 3 months in seconds (approximate)
+            _ => unreachable!("Should not reach here"),
+        };
+
+        // Get all unsaved entries older than the cutoff timestamp
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name FROM transcription_history WHERE saved = 0 AND timestamp < ?1",
+        )?;
+
+        let rows = stmt.query_map(params![cutoff_timestamp], |row| {
+            Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
+        })?;
+
+        let mut entries_to_delete: Vec<(i64, String)> = Vec::new();
+        for row in rows {
+            entries_to_delete.push(row?);
+        }
+
+        let deleted_count = self.delete_entries_and_files(&entries_to_delete)?;
+
+        if deleted_count > 0 {
+            debug!(
+                "Cleaned up {} old history entries based on retention period",
+                deleted_count
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history_with_edits ORDER BY timestamp DESC",
+            HISTORY_ENTRY_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], map_history_entry)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Fetch one page of history entries, newest first, matching the query filters.
+    pub async fn query_history(&self, query: &HistoryQuery) -> Result<HistoryPage> {
+        let conn = self.get_connection()?;
+        Self::query_history_with_conn(&conn, query)
+    }
+
+    fn query_history_with_conn(conn: &Connection, query: &HistoryQuery) -> Result<HistoryPage> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_HISTORY_PAGE_SIZE)
+            .clamp(1, MAX_HISTORY_PAGE_SIZE);
+
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+
+        if let Some(cursor) = query.cursor {
+            conditions.push("(timestamp, id) < (?, ?)");
+            values.push(Value::Integer(cursor.timestamp));
+            values.push(Value::Integer(cursor.id));
+        }
+        if let Some(from) = query.from_timestamp {
+            conditions.push("timestamp >= ?");
+            values.push(Value::Integer(from));
+        }
+        if let Some(to) = query.to_timestamp {
+            conditions.push("timestamp <= ?");
+            values.push(Value::Integer(to));
+        }
+        if query.saved_only {
+            conditions.push("saved = 1");
+        }
+        if query.post_processed_only {
+            conditions.push("post_processed_text IS NOT NULL");
+        }
+        if let Some(text) = query
+            .text_contains
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            conditions.push(
+                "(transcription_text LIKE ? ESCAPE '\\' OR post_processed_text LIKE ? ESCAPE '\\' \
+                 OR edited_text LIKE ? ESCAPE '\\')",
+            );
+            let pattern = format!("%{}%", escape_like(text));
+            values.push(Value::Text(pattern.clone()));
+            values.push(Value::Text(pattern.clone()));
+            values.push(Value::Text(pattern));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        // Fetch one extra row to find out whether another page exists
+        values.push(Value::Integer(limit as i64 + 1));
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history_with_edits {} ORDER BY timestamp DESC, id DESC LIMIT ?",
+            HISTORY_ENTRY_COLUMNS, where_clause
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(values.iter()), map_history_entry)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        let next_cursor = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|e| HistoryCursor {
+                timestamp: e.timestamp,
+                id: e.id,
+            })
+        } else {
+            None
+        };
+
+        Ok(HistoryPage {
+            entries,
+            next_cursor,
+        })
+    }
+
+    pub fn get_latest_entry(&self) -> Result<Option<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        Self::get_latest_entry_with_conn(&conn)
+    }
+
+    fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history_with_edits ORDER BY timestamp DESC LIMIT 1",
+            HISTORY_ENTRY_COLUMNS
+        ))?;
+
+        let entry = stmt.query_row([], map_history_entry).optional()?;
+
+        Ok(entry)
+    }
+
+    /// Full-text search over raw and post-processed transcription text.
+    /// Results are ordered by relevance (BM25), newest first on ties.
+    pub async fn search_history(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<HistorySearchResult>> {
+        let conn = self.get_connection()?;
+        Self::search_history_with_conn(&conn, query, limit, offset)
+    }
+
+    fn search_history_with_conn(
+        conn: &Connection,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<HistorySearchResult>> {
+        let fts_query = match build_fts_query(query) {
+            Some(q) => q,
+            None => return Ok(Vec::new()),
+        };
+
+        let columns = HISTORY_ENTRY_COLUMNS
+            .split(", ")
+            .map(|c| format!("h.{}", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {},
+                    snippet(transcription_history_fts, -1, '<mark>', '</mark>', '…', 12) AS snippet,
+                    bm25(transcription_history_fts) AS match_rank
+             FROM transcription_history_fts
+             JOIN transcription_history_with_edits h ON h.id = transcription_history_fts.rowid
+             WHERE transcription_history_fts MATCH ?1
+             ORDER BY match_rank, h.timestamp DESC
+             LIMIT ?2 OFFSET ?3",
+            columns
+        ))?;
+
+        let rows = stmt.query_map(params![fts_query, limit as i64, offset as i64], |row| {
+            Ok(HistorySearchResult {
+                entry: map_history_entry(row)?,
+                snippet: row.get("snippet")?,
+                rank: row.get("match_rank")?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        debug!(
+            "History search '{}' returned {} results",
+            query,
+            results.len()
+        );
+        Ok(results)
+    }
+
+    pub async fn toggle_saved_status(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+
+        // Get current saved status
+        let current_saved: bool = conn.query_row(
+            "SELECT saved FROM transcription_history WHERE id = ?1",
+            params![id],
+            |row| row.get("saved"),
+        )?;
+
+        let new_saved = !current_saved;
+
+        conn.execute(
+            "UPDATE transcription_history SET saved = ?1 WHERE id = ?2",
+            params![new_saved, id],
+        )?;
+
+        debug!("Toggled saved status for entry {}: {}", id, new_saved);
+
+        // Emit history updated event
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Export history entries to `path`, oldest first. When `ids` is given only
+    /// those entries are written. Returns the number of entries exported.
+    pub async fn export_history(
+        &self,
+        path: &Path,
+        format: HistoryExportFormat,
+        ids: Option<Vec<i64>>,
+    ) -> Result<usize> {
+        let mut entries = self.get_history_entries().await?;
+        if let Some(ids) = ids {
+            entries.retain(|entry| ids.contains(&entry.id));
+        }
+
+        Self::write_export_file(&entries, &self.recordings_dir, path, format)
+    }
+
+    /// Exports the whole history kept in `app_data_dir` to `path` without a
+    /// running app. Used by `--export-history`, which may run next to an open
+    /// instance.
+    pub fn export_from_data_dir(
+        app_data_dir: &Path,
+        path: &Path,
+        format: HistoryExportFormat,
+    ) -> Result<usize> {
+        let db_path = app_data_dir.join(DB_FILE_NAME);
+        if !db_path.exists() {
+            anyhow::bail!("No history found at {}", db_path.display());
+        }
+        Self::init_database(&db_path)?;
+
+        let conn = Connection::open(&db_path)?;
+        let entries = Self::get_all_entries_with_conn(&conn)?;
+
+        Self::write_export_file(
+            &entries,
+            &app_data_dir.join(RECORDINGS_DIR_NAME),
+            path,
+            format,
+        )
+    }
+
+    fn write_export_file(
+        entries: &[HistoryEntry],
+        recordings_dir: &Path,
+        path: &Path,
+        format: HistoryExportFormat,
+    ) -> Result<usize> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        write_export(&mut writer, entries, format, &|entry| {
+            wav_duration_secs(&recordings_dir.join(&entry.file_name))
+        })?;
+        writer.flush()?;
+
+        info!(
+            "Exported {} history entries to {:?} as {:?}",
+            entries.len(),
+            path,
+            format
+        );
+        Ok(entries.len())
+    }
+
+    /// Bundle the history database, including revisions, and all recordings
+    /// into a `.tar.gz` at `path`. Returns the number of recordings included.
+    pub fn export_archive(&self, path: &Path) -> Result<usize> {
+        let staging = staging_dir("history-export")?;
+        let result = self.export_archive_via(&staging, path);
+        if let Err(e) = fs::remove_dir_all(&staging) {
+            error!("Failed to remove staging directory {:?}: {}", staging, e);
+        }
+        result
+    }
+
+    fn export_archive_via(&self, staging: &Path, path: &Path) -> Result<usize> {
+        let conn = self.get_connection()?;
+
+        // VACUUM INTO gives a consistent snapshot even while the app is writing
+        let snapshot = staging.join(ARCHIVE_DB_NAME);
+        conn.execute(
+            "VACUUM INTO ?1",
+            params![snapshot.to_string_lossy().to_string()],
+        )?;
+
+        let recordings: Vec<PathBuf> = Self::get_all_entries_with_conn(&conn)?
+            .iter()
+            .map(|entry| self.get_audio_file_path(&entry.file_name))
+            .filter(|file_path| file_path.exists())
+            .collect();
+
+        let count = write_archive(path, &snapshot, &recordings)?;
+        info!(
+            "Exported history archive to {:?} ({} recordings)",
+            path, count
+        );
+        Ok(count)
+    }
+
+    /// Merge a history archive created by `export_archive` into this history.
+    /// Entries already present (same `file_name` and `timestamp`) are skipped;
+    /// new entries bring their revisions along.
+    /// Returns the number of entries imported.
+    pub fn import_archive(&self, path: &Path) -> Result<usize> {
+        let staging = staging_dir("history-import")?;
+        let result = self.import_archive_via(&staging, path);
+        if let Err(e) = fs::remove_dir_all(&staging) {
+            error!("Failed to remove staging directory {:?}: {}", staging, e);
+        }
+        result
+    }
+
+    fn import_archive_via(&self, staging: &Path, path: &Path) -> Result<usize> {
+        unpack_archive(path, staging)?;
+
+        // Bring archives from older versions up to the current schema
+        let mut archive_conn = Connection::open(staging.join(ARCHIVE_DB_NAME))?;
+        Self::migrate_from_tauri_plugin_sql(&archive_conn)?;
+        Migrations::new(MIGRATIONS.to_vec()).to_latest(&mut archive_conn)?;
+        // File names become recording paths, so they must not leave the
+        // recordings directory
+        let (archived, rejected): (Vec<_>, Vec<_>) =
+            Self::get_all_entries_with_conn(&archive_conn)?
+                .into_iter()
+                .partition(|entry| is_plain_file_name(&entry.file_name));
+        for entry in &rejected {
+            warn!(
+                "Skipping archived entry with invalid file name {:?}",
+                entry.file_name
+            );
+        }
+
+        let revisions = Self::get_all_revisions_with_conn(&archive_conn)?;
+
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        let imported = Self::merge_entries_with_conn(&tx, &archived, &revisions)?;
+        tx.commit()?;
+
+        let archive_recordings = staging.join(ARCHIVE_RECORDINGS_DIR);
+        for entry in &imported {
+            let source = archive_recordings.join(&entry.file_name);
+            let dest = self.get_audio_file_path(&entry.file_name);
+            if source.exists() && !dest.exists() {
+                if let Err(e) = fs::copy(&source, &dest) {
+                    error!("Failed to import recording {}: {}", entry.file_name, e);
+                }
+            }
+        }
+
+        info!(
+            "Imported {} of {} history entries from {:?}",
+            imported.len(),
+            archived.len(),
+            path
+        );
+
+        if !imported.is_empty() {
+            if let Err(e) = self.app_handle.emit("history-updated", ()) {
+                error!("Failed to emit history-updated event: {}", e);
+            }
+        }
+
+        Ok(imported.len())
+    }
+
+    fn get_all_entries_with_conn(conn: &Connection) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history_with_edits ORDER BY timestamp ASC, id ASC",
+            HISTORY_ENTRY_COLUMNS
+        ))?;
+
+        let entries = stmt
+            .query_map([], map_history_entry)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    fn get_all_revisions_with_conn(conn: &Connection) -> Result<Vec<HistoryRevision>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM history_revisions ORDER BY created_at ASC, id ASC",
+            HISTORY_REVISION_COLUMNS
+        ))?;
+
+        let revisions = stmt
+            .query_map([], map_history_revision)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(revisions)
+    }
+
+    /// Insert `entries` that don't already exist, matching on `file_name` and
+    /// `timestamp`, together with their `revisions`, which refer to the
+    /// entries by the ids in `entries`. Returns the entries that were inserted.
+    fn merge_entries_with_conn(
+        conn: &Connection,
+        entries: &[HistoryEntry],
+        revisions: &[HistoryRevision],
+    ) -> Result<Vec<HistoryEntry>> {
+        let mut exists_stmt = conn.prepare(
+            "SELECT 1 FROM transcription_history WHERE file_name = ?1 AND timestamp = ?2",
+        )?;
+        let mut insert_stmt = conn.prepare(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, \
+             model_id, engine_type, language, audio_duration_ms, transcription_ms, post_process_provider, post_process_model, post_process_ms, binding_id, timings) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+        )?;
+        let mut insert_revision_stmt = conn.prepare(
+            "INSERT INTO history_revisions (entry_id, kind, created_at, transcription_text, post_processed_text, post_process_prompt, metadata, original_text) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+
+        let mut inserted = Vec::new();
+        for entry in entries {
+            if exists_stmt.exists(params![entry.file_name, entry.timestamp])? {
+                continue;
+            }
+            let timings = entry
+                .timings
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?;
+
+            insert_stmt.execute(params![
+                entry.file_name,
+                entry.timestamp,
+                entry.saved,
+                entry.title,
+                entry.transcription_text,
+                entry.post_processed_text,
+                entry.post_process_prompt,
+                entry.metadata.model_id,
+                entry.metadata.engine_type,
+                entry.metadata.language,
+                entry.metadata.audio_duration_ms,
+                entry.metadata.transcription_ms,
+                entry.metadata.post_process_provider,
+                entry.metadata.post_process_model,
+                entry.metadata.post_process_ms,
+                entry.metadata.binding_id,
+                timings
+            ])?;
+
+            let id = conn.last_insert_rowid();
+
+            for revision in revisions.iter().filter(|r| r.entry_id == entry.id) {
+                insert_revision_stmt.execute(params![
+                    id,
+                    revision.kind.as_str(),
+                    revision.created_at,
+                    revision.transcription_text,
+                    revision.post_processed_text,
+                    revision.post_process_prompt,
+                    serde_json::to_string(&revision.metadata)?,
+                    revision.original_text
+                ])?;
+            }
+
+            inserted.push(HistoryEntry {
+                id,
+                ..entry.clone()
+            });
+        }
+
+        Ok(inserted)
+    }
+
+    /// Store a new revision of entry `entry_id`. The entry itself is left untouched.
+    pub fn add_revision(
+        &self,
+        entry_id: i64,
+        kind: HistoryRevisionKind,
+        transcription_text: String,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+        metadata: &TranscriptionMetadata,
+    ) -> Result<HistoryRevision> {
+        let conn = self.get_connection()?;
+        let revision = Self::add_revision_with_conn(
+            &conn,
+            entry_id,
+            kind,
+            &transcription_text,
+            post_processed_text.as_deref(),
+            post_process_prompt.as_deref(),
+            metadata,
+        )?;
+
+        debug!(
+            "Added {:?} revision {} for history entry {}",
+            kind, revision.id, entry_id
+        );
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(revision)
+    }
+
+    fn add_revision_with_conn(
+        conn: &Connection,
+        entry_id: i64,
+        kind: HistoryRevisionKind,
+        transcription_text: &str,
+        post_processed_text: Option<&str>,
+        post_process_prompt: Option<&str>,
+        metadata: &TranscriptionMetadata,
+    ) -> Result<HistoryRevision> {
+        let exists = conn
+            .prepare("SELECT 1 FROM transcription_history WHERE id = ?1")?
+            .exists(params![entry_id])?;
+        if !exists {
+            return Err(anyhow::anyhow!("History entry {} not found", entry_id));
+        }
+
+        conn.execute(
+            "INSERT INTO history_revisions (entry_id, kind, created_at, transcription_text, post_processed_text, post_process_prompt, metadata) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry_id,
+                kind.as_str(),
+                Utc::now().timestamp(),
+                transcription_text,
+                post_processed_text,
+                post_process_prompt,
+                serde_json::to_string(metadata)?
+            ],
+        )?;
+
+        let revision = conn.query_row(
+            &format!(
+                "SELECT {} FROM history_revisions WHERE id = ?1",
+                HISTORY_REVISION_COLUMNS
+            ),
+            params![conn.last_insert_rowid()],
+            map_history_revision,
+        )?;
+
+        Ok(revision)
+    }
+
+    /// All revisions of an entry, oldest first.
+    pub fn get_revisions(&self, entry_id: i64) -> Result<Vec<HistoryRevision>> {
+        let conn = self.get_connection()?;
+        Self::get_revisions_with_conn(&conn, entry_id)
+    }
+
+    fn get_revisions_with_conn(conn: &Connection, entry_id: i64) -> Result<Vec<HistoryRevision>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM history_revisions WHERE entry_id = ?1 ORDER BY created_at ASC, id ASC",
+            HISTORY_REVISION_COLUMNS
+        ))?;
+
+        let revisions = stmt
+            .query_map(params![entry_id], map_history_revision)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(revisions)
+    }
+
+    /// Suggest custom words and replacement rules learned from the user's manual edits.
+    pub fn get_correction_suggestions(&self) -> Result<Vec<CorrectionSuggestion>> {
+        let conn = self.get_connection()?;
+        let corrections = Self::get_corrections_with_conn(&conn)?;
+        let settings = crate::settings::get_settings(&self.app_handle);
+
+        Ok(collect_suggestions(
+            &corrections,
+            &settings.custom_words,
+            settings.word_correction_threshold,
+            &settings.replacement_rules,
+            &settings.rejected_correction_suggestions,
+        ))
+    }
+
+    /// `(entry_id, transcription_text, edited_text)` for every edited entry
+    fn get_corrections_with_conn(conn: &Connection) -> Result<Vec<(i64, String, String)>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, transcription_text, edited_text FROM transcription_history_with_edits
+             WHERE edited_text IS NOT NULL ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let corrections = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(corrections)
+    }
+
+    pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
+        self.recordings_dir.join(file_name)
+    }
+
+    pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        Self::get_entry_by_id_with_conn(&conn, id)
+    }
+
+    fn get_entry_by_id_with_conn(conn: &Connection, id: i64) -> Result<Option<HistoryEntry>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history_with_edits WHERE id = ?1",
+            HISTORY_ENTRY_COLUMNS
+        ))?;
+
+        let entry = stmt.query_row([id], map_history_entry).optional()?;
+
+        Ok(entry)
+    }
+
+    /// Record a manual correction of an entry's text. The original row is kept
+    /// as-is; the edit is stored as a revision and exposed as `edited_text`.
+    pub fn update_entry_text(&self, id: i64, text: &str) -> Result<HistoryEntry> {
+        let conn = self.get_connection()?;
+        let entry = Self::update_entry_text_with_conn(&conn, id, text)?;
+
+        debug!("Updated text of history entry {}", id);
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(entry)
+    }
+
+    fn update_entry_text_with_conn(conn: &Connection, id: i64, text: &str) -> Result<HistoryEntry> {
+        let entry = Self::get_entry_by_id_with_conn(conn, id)?
+            .ok_or_else(|| anyhow::anyhow!("History entry {} not found", id))?;
+
+        if text.trim().is_empty() {
+            return Err(anyhow::anyhow!("Edited text cannot be empty"));
+        }
+
+        // Saving without changes shouldn't add to the revision history
+        if text == entry.final_text() {
+            return Ok(entry);
+        }
+
+        conn.execute(
+            "INSERT INTO history_revisions (entry_id, kind, created_at, transcription_text, original_text) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id,
+                HistoryRevisionKind::Edit.as_str(),
+                Utc::now().timestamp(),
+                text,
+                entry.final_text()
+            ],
+        )?;
+
+        Self::get_entry_by_id_with_conn(conn, id)?
+            .ok_or_else(|| anyhow::anyhow!("History entry {} not found", id))
+    }
+
+    pub async fn delete_entry(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+
+        // Get the entry to find the file name
+        if let Some(entry) = self.get_entry_by_id(id).await? {
+            // Delete the audio file first
+            let file_path = self.get_audio_file_path(&entry.file_name);
+            if file_path.exists() {
+                if let Err(e) = fs::remove_file(&file_path) {
+                    error!("Failed to delete audio file {}: {}", entry.file_name, e);
+                    // Continue with database deletion even if file deletion fails
+                }
+            }
+        }
+
+        // Delete from database
+        conn.execute(
+            "DELETE FROM transcription_history WHERE id = ?1",
+            params![id],
+        )?;
+
+        debug!("Deleted history entry with id: {}", id);
+
+        // Emit history updated event
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    fn format_timestamp_title(&self, timestamp: i64) -> String {
+        if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
+            // Convert UTC to local timezone
+            let local_datetime = utc_datetime.with_timezone(&Local);
+            local_datetime.format("%B %e, %Y - %l:%M%p").to_string()
+        } else {
+            format!("Recording {}", timestamp)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::{params, Connection};
+
+    fn setup_conn() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("open in-memory db");
+        Migrations::new(MIGRATIONS.to_vec())
+            .to_latest(&mut conn)
+            .expect("run migrations");
+        conn
+    }
+
+    fn insert_entry(conn: &Connection, timestamp: i64, text: &str, post_processed: Option<&str>) {
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                format!("handy-{}.wav", timestamp),
+                timestamp,
+                false,
+                format!("Recording {}", timestamp),
+                text,
+                post_processed,
+                Option::<String>::None
+            ],
+        )
+        .expect("insert history entry");
+    }
+
+    #[test]
+    fn export_from_data_dir_reads_the_database_directly() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("history.jsonl");
+        assert!(
+            HistoryManager::export_from_data_dir(dir.path(), &out, HistoryExportFormat::Jsonl)
+                .is_err()
+        );
+
+        let db_path = dir.path().join(DB_FILE_NAME);
+        HistoryManager::init_database(&db_path).unwrap();
+        let conn = Connection::open(&db_path).unwrap();
+        insert_entry(&conn, 100, "first", None);
+        insert_entry(&conn, 200, "second", None);
+
+        let count =
+            HistoryManager::export_from_data_dir(dir.path(), &out, HistoryExportFormat::Jsonl)
+                .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(fs::read_to_string(&out).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn recordings_saved_in_the_same_millisecond_get_distinct_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+
+        let first = reserve_recording_file(dir.path(), now).unwrap();
+        let second = reserve_recording_file(dir.path(), now).unwrap();
+
+        assert_eq!(first, "handy-1700000000123.wav");
+        assert_eq!(second, "handy-1700000000123-1.wav");
+        assert!(dir.path().join(&first).exists());
+    }
+
+    #[test]
+    fn merge_entries_skips_existing_file_name_and_timestamp() {
+        let source = setup_conn();
+        insert_entry(&source, 100, "first", None);
+        insert_entry(&source, 200, "second", Some("processed"));
+        let archived = HistoryManager::get_all_entries_with_conn(&source).expect("read source");
+
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "first", None);
+
+        let imported =
+            HistoryManager::merge_entries_with_conn(&conn, &archived, &[]).expect("merge entries");
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].timestamp, 200);
+        assert_eq!(
+            imported[0].post_processed_text.as_deref(),
+            Some("processed")
+        );
+
+        // Importing the same archive again is a no-op
+        let imported =
+            HistoryManager::merge_entries_with_conn(&conn, &archived, &[]).expect("merge entries");
+        assert!(imported.is_empty());
+
+        let all = HistoryManager::get_all_entries_with_conn(&conn).expect("read merged");
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn merge_entries_preserves_metadata_and_timings() {
+        let conn = setup_conn();
+        let metadata = TranscriptionMetadata {
+            model_id: Some("parakeet-tdt-0.6b-v3".to_string()),
+            engine_type: Some("Parakeet".to_string()),
+            language: Some("auto".to_string()),
+            audio_duration_ms: Some(4200),
+            transcription_ms: Some(310),
+            post_process_provider: Some("openai".to_string()),
+            post_process_model: Some("gpt-4o-mini".to_string()),
+            post_process_ms: Some(900),
+            binding_id: Some("transcribe".to_string()),
+        };
+        let entry = HistoryEntry {
+            id: 0,
+            file_name: "handy-100.wav".to_string(),
+            timestamp: 100,
+            saved: false,
+            title: "Recording 100".to_string(),
+            transcription_text: "hello".to_string(),
+            post_processed_text: None,
+            post_process_prompt: None,
+            metadata: metadata.clone(),
+            edited_text: None,
+            timings: Some(TranscriptionTimings {
+                segments: vec![TranscriptionSegment {
+                    start_ms: 0,
+                    end_ms: 4200,
+                    text: "hello".to_string(),
+                }],
+                words: None,
+            }),
+        };
+
+        HistoryManager::merge_entries_with_conn(&conn, &[entry.clone()], &[])
+            .expect("merge entries");
+
+        let latest = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+        assert_eq!(latest.metadata, metadata);
+        assert_eq!(latest.timings, entry.timings);
+    }
+
+    #[test]
+    fn merge_entries_carries_revisions_to_the_new_ids() {
+        let source = setup_conn();
+        insert_entry(&source, 100, "first", None);
+        insert_entry(&source, 200, "teh second", None);
+        let second = HistoryManager::get_latest_entry_with_conn(&source)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+        HistoryManager::update_entry_text_with_conn(&source, second.id, "the second")
+            .expect("edit entry");
+        let archived = HistoryManager::get_all_entries_with_conn(&source).expect("read source");
+        let revisions =
+            HistoryManager::get_all_revisions_with_conn(&source).expect("read revisions");
+
+        // Occupy the archived ids so the imported entries get different ones
+        let conn = setup_conn();
+        insert_entry(&conn, 300, "third", None);
+        insert_entry(&conn, 400, "fourth", None);
+
+        let imported = HistoryManager::merge_entries_with_conn(&conn, &archived, &revisions)
+            .expect("merge entries");
+        let imported_second = imported
+            .iter()
+            .find(|entry| entry.timestamp == 200)
+            .expect("second entry imported");
+        assert_ne!(imported_second.id, second.id);
+
+        let entry = HistoryManager::get_entry_by_id_with_conn(&conn, imported_second.id)
+            .expect("fetch entry")
+            .expect("entry exists");
+        assert_eq!(entry.edited_text.as_deref(), Some("the second"));
+
+        let revisions = HistoryManager::get_revisions_with_conn(&conn, imported_second.id)
+            .expect("fetch revisions");
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].kind, HistoryRevisionKind::Edit);
+        assert_eq!(revisions[0].original_text.as_deref(), Some("teh second"));
+
+        let first = imported
+            .iter()
+            .find(|entry| entry.timestamp == 100)
+            .expect("first entry imported");
+        assert!(HistoryManager::get_revisions_with_conn(&conn, first.id)
+            .expect("fetch revisions")
+            .is_empty());
+    }
+
+    #[test]
+    fn revisions_are_linked_to_entry_and_removed_with_it() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "original", None);
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+
+        let metadata = TranscriptionMetadata {
+            model_id: Some("large".to_string()),
+            ..Default::default()
+        };
+        let revision = HistoryManager::add_revision_with_conn(
+            &conn,
+            entry.id,
+            HistoryRevisionKind::Retranscription,
+            "better",
+            None,
+            None,
+            &metadata,
+        )
+        .expect("add revision");
+        assert_eq!(revision.entry_id, entry.id);
+        assert_eq!(revision.metadata, metadata);
+
+        // The original entry is untouched
+        let reloaded = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+        assert_eq!(reloaded.transcription_text, "original");
+
+        let revisions =
+            HistoryManager::get_revisions_with_conn(&conn, entry.id).expect("fetch revisions");
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].transcription_text, "better");
+
+        conn.execute(
+            "DELETE FROM transcription_history WHERE id = ?1",
+            params![entry.id],
+        )
+        .expect("delete entry");
+        let revisions =
+            HistoryManager::get_revisions_with_conn(&conn, entry.id).expect("fetch revisions");
+        assert!(revisions.is_empty());
+    }
+
+    #[test]
+    fn add_revision_rejects_unknown_entry() {
+        let conn = setup_conn();
+        let result = HistoryManager::add_revision_with_conn(
+            &conn,
+            42,
+            HistoryRevisionKind::Retranscription,
+            "text",
+            None,
+            None,
+            &TranscriptionMetadata::default(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn edits_are_exposed_as_latest_edited_text() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "helo world", Some("Helo world."));
+        let id = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists")
+            .id;
+
+        let entry = HistoryManager::update_entry_text_with_conn(&conn, id, "Hello world.")
+            .expect("edit entry");
+        assert_eq!(entry.edited_text.as_deref(), Some("Hello world."));
+        assert_eq!(entry.transcription_text, "helo world");
+        assert_eq!(entry.final_text(), "Hello world.");
+
+        let entry = HistoryManager::update_entry_text_with_conn(&conn, id, "Hello, world!")
+            .expect("edit entry again");
+        assert_eq!(entry.edited_text.as_deref(), Some("Hello, world!"));
+
+        let revisions = HistoryManager::get_revisions_with_conn(&conn, id).expect("revisions");
+        assert_eq!(revisions.len(), 2);
+        assert!(revisions
+            .iter()
+            .all(|r| r.kind == HistoryRevisionKind::Edit));
+        assert_eq!(revisions[0].original_text.as_deref(), Some("Helo world."));
+        assert_eq!(revisions[1].original_text.as_deref(), Some("Hello world."));
+
+        // Unchanged text does not create a revision
+        HistoryManager::update_entry_text_with_conn(&conn, id, "Hello, world!")
+            .expect("no-op edit");
+        let revisions = HistoryManager::get_revisions_with_conn(&conn, id).expect("revisions");
+        assert_eq!(revisions.len(), 2);
+    }
+
+    #[test]
+    fn corrections_come_from_edited_entries() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "we use charge bee", None);
+        insert_entry(&conn, 200, "untouched", None);
+        let id = HistoryManager::get_entry_by_id_with_conn(&conn, 1)
+            .expect("fetch entry")
+            .expect("entry exists")
+            .id;
+        HistoryManager::update_entry_text_with_conn(&conn, id, "we use ChargeBee")
+            .expect("edit entry");
+
+        let corrections = HistoryManager::get_corrections_with_conn(&conn).expect("corrections");
+        assert_eq!(
+            corrections,
+            vec![(
+                id,
+                "we use charge bee".to_string(),
+                "we use ChargeBee".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn edit_rejects_empty_text_and_unknown_entries() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "text", None);
+        let id = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists")
+            .id;
+
+        assert!(HistoryManager::update_entry_text_with_conn(&conn, id, "   ").is_err());
+        assert!(HistoryManager::update_entry_text_with_conn(&conn, id + 1, "text").is_err());
+    }
+
+    #[test]
+    fn get_latest_entry_returns_none_when_empty() {
+        let conn = setup_conn();
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn).expect("fetch latest entry");
+        assert!(entry.is_none());
+    }
+
+    #[test]
+    fn get_latest_entry_returns_newest_entry() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "first", None);
+        insert_entry(&conn, 200, "second", Some("processed"));
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+
+        assert_eq!(entry.timestamp, 200);
+        assert_eq!(entry.transcription_text, "second");
+        assert_eq!(entry.post_processed_text.as_deref(), Some("processed"));
+    }
+
+    #[test]
+    fn build_fts_query_quotes_terms_and_prefixes_last() {
+        assert_eq!(build_fts_query("   "), None);
+        assert_eq!(
+            build_fts_query("hello wor"),
+            Some("\"hello\" \"wor\"*".to_string())
+        );
+        assert_eq!(
+            build_fts_query("say \"hi\" OR"),
+            Some("\"say\" \"\"\"hi\"\"\" \"OR\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn search_history_matches_raw_and_post_processed_text() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "meeting notes about the roadmap", None);
+        insert_entry(
+            &conn,
+            200,
+            "grocery list",
+            Some("Grocery list: apples, roadmap snacks"),
+        );
+        insert_entry(&conn, 300, "unrelated dictation", None);
+
+        let results = HistoryManager::search_history_with_conn(&conn, "roadmap", 10, 0)
+            .expect("search history");
+
+        let mut timestamps: Vec<i64> = results.iter().map(|r| r.entry.timestamp).collect();
+        timestamps.sort();
+        assert_eq!(timestamps, vec![100, 200]);
+        assert!(results.iter().all(|r| r.snippet.contains("<mark>")));
+    }
+
+    #[test]
+    fn search_history_supports_prefix_and_pagination() {
+        let conn = setup_conn();
+        for i in 0..5 {
+            insert_entry(&conn, 100 + i, "transcription sample", None);
+        }
+
+        let first_page = HistoryManager::search_history_with_conn(&conn, "transcr", 2, 0)
+            .expect("search first page");
+        let second_page = HistoryManager::search_history_with_conn(&conn, "transcr", 2, 2)
+            .expect("search second page");
+
+        assert_eq!(first_page.len(), 2);
+        assert_eq!(second_page.len(), 2);
+        assert!(first_page
+            .iter()
+            .all(|a| second_page.iter().all(|b| a.entry.id != b.entry.id)));
+    }
+
+    #[test]
+    fn search_index_follows_updates_and_deletes() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "original wording", None);
+
+        conn.execute(
+            "UPDATE transcription_history SET transcription_text = 'revised wording' WHERE timestamp = 100",
+            [],
+        )
+        .expect("update entry");
+        assert!(
+            HistoryManager::search_history_with_conn(&conn, "original", 10, 0)
+                .expect("search")
+                .is_empty()
+        );
+        assert_eq!(
+            HistoryManager::search_history_with_conn(&conn, "revised", 10, 0)
+                .expect("search")
+                .len(),
+            1
+        );
+
+        conn.execute(
+            "DELETE FROM transcription_history WHERE timestamp = 100",
+            [],
+        )
+        .expect("delete entry");
+        assert!(
+            HistoryManager::search_history_with_conn(&conn, "revised", 10, 0)
+                .expect("search")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn search_index_follows_manual_edits() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "meeting notes", None);
+        let id = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists")
+            .id;
+        let search = |query: &str| {
+            HistoryManager::search_history_with_conn(&conn, query, 10, 0)
+                .expect("search")
+                .len()
+        };
+
+        HistoryManager::update_entry_text_with_conn(&conn, id, "meeting minutes")
+            .expect("edit entry");
+        assert_eq!(search("minutes"), 1);
+        assert_eq!(search("notes"), 1);
+
+        // Only the latest edit is indexed
+        HistoryManager::update_entry_text_with_conn(&conn, id, "meeting summary")
+            .expect("edit entry again");
+        assert_eq!(search("minutes"), 0);
+        assert_eq!(search("summary"), 1);
+
+        conn.execute("DELETE FROM transcription_history WHERE id = ?1", [id])
+            .expect("delete entry");
+        assert_eq!(search("summary"), 0);
+    }
+
+    #[test]
+    fn query_history_paginates_with_cursor() {
+        let conn = setup_conn();
+        // Two entries share a timestamp so the id tiebreaker is exercised
+        insert_entry(&conn, 100, "a", None);
+        insert_entry(&conn, 200, "b", None);
+        insert_entry(&conn, 200, "c", None);
+        insert_entry(&conn, 300, "d", None);
+
+        let mut query = HistoryQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first = HistoryManager::query_history_with_conn(&conn, &query).expect("first page");
+        let texts: Vec<&str> = first
+            .entries
+            .iter()
+            .map(|e| e.transcription_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["d", "c"]);
+        assert!(first.next_cursor.is_some());
+
+        query.cursor = first.next_cursor;
+        let second = HistoryManager::query_history_with_conn(&conn, &query).expect("second page");
+        let texts: Vec<&str> = second
+            .entries
+            .iter()
+            .map(|e| e.transcription_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["b", "a"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn query_history_applies_filters() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "old note", None);
+        insert_entry(&conn, 200, "draft email", Some("Polished email"));
+        insert_entry(&conn, 300, "100% sure", None);
+        conn.execute(
+            "UPDATE transcription_history SET saved = 1 WHERE timestamp = 300",
+            [],
+        )
+        .expect("mark saved");
+
+        let run = |query: HistoryQuery| -> Vec<i64> {
+            HistoryManager::query_history_with_conn(&conn, &query)
+                .expect("query history")
+                .entries
+                .iter()
+                .map(|e| e.timestamp)
+                .collect()
+        };
+
+        assert_eq!(
+            run(HistoryQuery {
+                from_timestamp: Some(150),
+                to_timestamp: Some(250),
+                ..Default::default()
+            }),
+            vec![200]
+        );
+        assert_eq!(
+            run(HistoryQuery {
+                saved_only: true,
+                ..Default::default()
+            }),
+            vec![300]
+        );
+        assert_eq!(
+            run(HistoryQuery {
+                post_processed_only: true,
+                ..Default::default()
+            }),
+            vec![200]
+        );
+        assert_eq!(
+            run(HistoryQuery {
+                text_contains: Some("POLISHED".to_string()),
+                ..Default::default()
+            }),
+            vec![200]
+        );
+        // LIKE wildcards in the search text are matched literally
+        assert_eq!(
+            run(HistoryQuery {
+                text_contains: Some("0%".to_string()),
+                ..Default::default()
+            }),
+            vec![300]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Timing information for transcriptions.
+//!
+//! Engines that report timestamps give either phrase-level segments or
+//! individual words. Word-level output is grouped into segments here so the
+//! UI and subtitle export always have segments to work with.
+
+use super::TranscriptionMetadata;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// A pause between words at least this long starts a new segment
+const SEGMENT_BREAK_PAUSE_MS: u64 = 800;
+
+/// A span of the recording and the text spoken in it. Times are milliseconds
+/// from the start of the audio.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptionSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// Segment and word timings stored alongside a history entry.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptionTimings {
+    pub segments: Vec<TranscriptionSegment>,
+    /// Per-word timings, for engines that report them
+    #[serde(default)]
+    pub words: Option<Vec<TranscriptionSegment>>,
+}
+
+/// Transcribed text together with its timings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    pub segments: Vec<TranscriptionSegment>,
+    /// Per-word timings, for engines that report them
+    pub words: Option<Vec<TranscriptionSegment>>,
+    /// Model, engine and language that produced the text
+    #[serde(skip)]
+    pub metadata: TranscriptionMetadata,
+}
+
+impl TranscriptionOutput {
+    /// Timings worth storing, or `None` if the engine reported none
+    pub fn timings(&self) -> Option<TranscriptionTimings> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        Some(TranscriptionTimings {
+            segments: self.segments.clone(),
+            words: self.words.clone(),
+        })
+    }
+
+    /// Moves every timestamp `offset_ms` later, for output of a chunk that
+    /// started partway through the audio.
+    pub fn shift(&mut self, offset_ms: u64) {
+        let words = self.words.iter_mut().flatten();
+        for segment in self.segments.iter_mut().chain(words) {
+            segment.start_ms += offset_ms;
+            segment.end_ms += offset_ms;
+        }
+    }
+}
+
+/// Groups word timings into sentence-like segments, breaking after sentence
+/// punctuation or at a long pause.
+pub fn group_words(words: &[TranscriptionSegment]) -> Vec<TranscriptionSegment> {
+    let mut segments: Vec<TranscriptionSegment> = Vec::new();
+    let mut current: Option<TranscriptionSegment> = None;
+
+    for word in words {
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        if let Some(segment) = current.take() {
+            if word.start_ms.saturating_sub(segment.end_ms) >= SEGMENT_BREAK_PAUSE_MS {
+                segments.push(segment);
+            } else {
+                current = Some(segment);
+            }
+        }
+
+        let segment = current.get_or_insert_with(|| TranscriptionSegment {
+            start_ms: word.start_ms,
+            end_ms: word.end_ms,
+            text: String::new(),
+        });
+        if !segment.text.is_empty() {
+            segment.text.push(' ');
+        }
+        segment.text.push_str(text);
+        segment.end_ms = word.end_ms;
+
+        if text.ends_with(['.', '?', '!']) {
+            segments.extend(current.take());
+        }
+    }
+
+    segments.extend(current);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(start_ms: u64, end_ms: u64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_words_at_sentence_ends_and_pauses() {
+        let words = vec![
+            word(0, 200, " Hello"),
+            word(250, 500, " world."),
+            word(600, 800, " How"),
+            word(850, 1000, " are"),
+            word(2000, 2300, " you"),
+        ];
+
+        assert_eq!(
+            group_words(&words),
+            vec![
+                word(0, 500, "Hello world."),
+                word(600, 1000, "How are"),
+                word(2000, 2300, "you"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shift_moves_segments_and_words() {
+        let mut output = TranscriptionOutput {
+            text: "hi".to_string(),
+            segments: vec![word(0, 500, "hi")],
+            words: Some(vec![word(100, 400, "hi")]),
+            ..Default::default()
+        };
+        output.shift(30_000);
+
+        assert_eq!(output.segments, vec![word(30_000, 30_500, "hi")]);
+        assert_eq!(output.words, Some(vec![word(30_100, 30_400, "hi")]));
+    }
+
+    #[test]
+    fn timings_are_none_without_segments() {
+        let output = TranscriptionOutput {
+            text: "hi".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(output.timings(), None);
+    }
+}
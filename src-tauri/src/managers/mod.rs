@@ -1,4 +1,11 @@
 pub mod audio;
+pub mod batch;
 pub mod history;
 pub mod model;
+pub mod model_catalog;
+pub mod model_descriptor;
+pub mod model_import;
+pub mod model_integrity;
+pub mod model_store;
+pub mod streaming;
 pub mod transcription;
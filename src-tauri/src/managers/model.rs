@@ -1,3 +1,14 @@
+use crate::managers::model_catalog::{load_catalog, refresh_remote_catalog};
+use crate::managers::model_descriptor::{display_name, ModelDescriptor, DESCRIPTOR_FILE_NAME};
+use crate::managers::model_import::{
+    classify, copy_dir, copy_file, extract_archive, file_name_from_url, total_size, ImportKind,
+    ImportSource,
+};
+use crate::managers::model_integrity::{
+    sha256_file, verify_directory_checksums, write_directory_checksums, ModelIntegrity,
+    ModelVerification, StreamHasher,
+};
+use crate::managers::model_store::{find_in, migrate, validate_new_dir, ModelsDirMigration};
 use crate::settings::{get_settings, write_settings};
 use anyhow::Result;
 use flate2::read::GzDecoder;
@@ -11,10 +22,10 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 use tar::Archive;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter, Manager, Runtime, Wry};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum EngineType {
@@ -44,6 +55,7 @@ pub struct ModelInfo {
     pub is_recommended: bool,       // Whether this is the recommended model for new users
     pub supported_languages: Vec<String>, // Languages this model can transcribe
     pub is_custom: bool,            // Whether this is a user-provided custom model
+    pub sha256: Option<String>,     // Expected SHA-256 of the downloaded file, if known
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -54,360 +66,56 @@ pub struct DownloadProgress {
     pub percentage: f64,
 }
 
-pub struct ModelManager {
-    app_handle: AppHandle,
-    models_dir: PathBuf,
+pub struct ModelManager<R: Runtime = Wry> {
+    app_handle: AppHandle<R>,
+    /// Where models are downloaded and imported to
+    models_dir: RwLock<PathBuf>,
+    /// Read-only directories searched for models after `models_dir`
+    search_paths: RwLock<Vec<PathBuf>>,
     available_models: Mutex<HashMap<String, ModelInfo>>,
     cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     extracting_models: Arc<Mutex<HashSet<String>>>,
+    /// Custom models whose descriptor couldn't be read, by directory name
+    discovery_errors: Mutex<HashMap<String, String>>,
+    /// Last failure to load each model into its engine
+    load_errors: Mutex<HashMap<String, String>>,
 }
 
-impl ModelManager {
-    pub fn new(app_handle: &AppHandle) -> Result<Self> {
-        // Create models directory in app data
-        let models_dir = app_handle
-            .path()
-            .app_data_dir()
-            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
-            .join("models");
+impl<R: Runtime> ModelManager<R> {
+    pub fn new(app_handle: &AppHandle<R>) -> Result<Self> {
+        // Use the configured models directory, or `models` in app data
+        let settings = get_settings(app_handle);
+        let models_dir = match settings.models_dir.as_deref().map(str::trim) {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => Self::default_models_dir(app_handle)?,
+        };
+        let search_paths: Vec<PathBuf> = settings
+            .model_search_paths
+            .iter()
+            .map(PathBuf::from)
+            .collect();
 
         if !models_dir.exists() {
             fs::create_dir_all(&models_dir)?;
         }
 
-        let mut available_models = HashMap::new();
-
-        // Whisper supported languages (99 languages from tokenizer)
-        // Including zh-Hans and zh-Hant variants to match frontend language codes
-        let whisper_languages: Vec<String> = vec![
-            "en", "zh", "zh-Hans", "zh-Hant", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl",
-            "ca", "nl", "ar", "sv", "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs",
-            "ro", "da", "hu", "ta", "no", "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy",
-            "sk", "te", "fa", "lv", "bn", "sr", "az", "sl", "kn", "et", "mk", "br", "eu", "is",
-            "hy", "ne", "mn", "bs", "kk", "sq", "sw", "gl", "mr", "pa", "si", "km", "sn", "yo",
-            "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am", "yi", "lo", "uz", "fo", "ht",
-            "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as", "tt", "haw", "ln",
-            "ha", "ba", "jw", "su", "yue",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        // TODO this should be read from a JSON file or something..
-        available_models.insert(
-            "small".to_string(),
-            ModelInfo {
-                id: "small".to_string(),
-                name: "Whisper Small".to_string(),
-                description: "Fast and fairly accurate.".to_string(),
-                filename: "ggml-small.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
-                size_mb: 487,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.60,
-                speed_score: 0.85,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        // Add downloadable models
-        available_models.insert(
-            "medium".to_string(),
-            ModelInfo {
-                id: "medium".to_string(),
-                name: "Whisper Medium".to_string(),
-                description: "Good accuracy, medium speed".to_string(),
-                filename: "whisper-medium-q4_1.bin".to_string(),
-                url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
-                size_mb: 492, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.75,
-                speed_score: 0.60,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "turbo".to_string(),
-            ModelInfo {
-                id: "turbo".to_string(),
-                name: "Whisper Turbo".to_string(),
-                description: "Balanced accuracy and speed.".to_string(),
-                filename: "ggml-large-v3-turbo.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
-                size_mb: 1600, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.80,
-                speed_score: 0.40,
-                supports_translation: false, // Turbo doesn't support translation
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "large".to_string(),
-            ModelInfo {
-                id: "large".to_string(),
-                name: "Whisper Large".to_string(),
-                description: "Good accuracy, but slow.".to_string(),
-                filename: "ggml-large-v3-q5_0.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
-                size_mb: 1100, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.30,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "breeze-asr".to_string(),
-            ModelInfo {
-                id: "breeze-asr".to_string(),
-                name: "Breeze ASR".to_string(),
-                description: "Optimized for Taiwanese Mandarin. Code-switching support."
-                    .to_string(),
-                filename: "breeze-asr-q5_k.bin".to_string(),
-                url: Some("https://blob.handy.computer/breeze-asr-q5_k.bin".to_string()),
-                size_mb: 1080,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.35,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: whisper_languages,
-                is_custom: false,
-            },
-        );
-
-        // Add NVIDIA Parakeet models (directory-based)
-        available_models.insert(
-            "parakeet-tdt-0.6b-v2".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v2".to_string(),
-                name: "Parakeet V2".to_string(),
-                description: "English only. The best model for English speakers.".to_string(),
-                filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
-                size_mb: 473, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.85,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        // Parakeet V3 supported languages (25 EU languages + Russian/Ukrainian):
-        // bg, hr, cs, da, nl, en, et, fi, fr, de, el, hu, it, lv, lt, mt, pl, pt, ro, sk, sl, es, sv, ru, uk
-        let parakeet_v3_languages: Vec<String> = vec![
-            "bg", "hr", "cs", "da", "nl", "en", "et", "fi", "fr", "de", "el", "hu", "it", "lv",
-            "lt", "mt", "pl", "pt", "ro", "sk", "sl", "es", "sv", "ru", "uk",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        available_models.insert(
-            "parakeet-tdt-0.6b-v3".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v3".to_string(),
-                name: "Parakeet V3".to_string(),
-                description: "Fast and accurate. Supports 25 European languages.".to_string(),
-                filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
-                size_mb: 478, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.80,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: true,
-                supported_languages: parakeet_v3_languages,
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-base".to_string(),
-            ModelInfo {
-                id: "moonshine-base".to_string(),
-                name: "Moonshine Base".to_string(),
-                description: "Very fast, English only. Handles accents well.".to_string(),
-                filename: "moonshine-base".to_string(),
-                url: Some("https://blob.handy.computer/moonshine-base.tar.gz".to_string()),
-                size_mb: 58,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Moonshine,
-                accuracy_score: 0.70,
-                speed_score: 0.90,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-tiny-streaming-en".to_string(),
-            ModelInfo {
-                id: "moonshine-tiny-streaming-en".to_string(),
-                name: "Moonshine V2 Tiny".to_string(),
-                description: "Ultra-fast, English only".to_string(),
-                filename: "moonshine-tiny-streaming-en".to_string(),
-                url: Some(
-                    "https://blob.handy.computer/moonshine-tiny-streaming-en.tar.gz".to_string(),
-                ),
-                size_mb: 31,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::MoonshineStreaming,
-                accuracy_score: 0.55,
-                speed_score: 0.95,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-small-streaming-en".to_string(),
-            ModelInfo {
-                id: "moonshine-small-streaming-en".to_string(),
-                name: "Moonshine V2 Small".to_string(),
-                description: "Fast, English only. Good balance of speed and accuracy.".to_string(),
-                filename: "moonshine-small-streaming-en".to_string(),
-                url: Some(
-                    "https://blob.handy.computer/moonshine-small-streaming-en.tar.gz".to_string(),
-                ),
-                size_mb: 100,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::MoonshineStreaming,
-                accuracy_score: 0.65,
-                speed_score: 0.90,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-medium-streaming-en".to_string(),
-            ModelInfo {
-                id: "moonshine-medium-streaming-en".to_string(),
-                name: "Moonshine V2 Medium".to_string(),
-                description: "English only. High quality.".to_string(),
-                filename: "moonshine-medium-streaming-en".to_string(),
-                url: Some(
-                    "https://blob.handy.computer/moonshine-medium-streaming-en.tar.gz".to_string(),
-                ),
-                size_mb: 192,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::MoonshineStreaming,
-                accuracy_score: 0.75,
-                speed_score: 0.80,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        // SenseVoice supported languages
-        let sense_voice_languages: Vec<String> =
-            vec!["zh", "zh-Hans", "zh-Hant", "en", "yue", "ja", "ko"]
-                .into_iter()
-                .map(String::from)
-                .collect();
+        let mut available_models = load_catalog(&models_dir);
 
-        available_models.insert(
-            "sense-voice-int8".to_string(),
-            ModelInfo {
-                id: "sense-voice-int8".to_string(),
-                name: "SenseVoice".to_string(),
-                description: "Very fast. Chinese, English, Japanese, Korean, Cantonese."
-                    .to_string(),
-                filename: "sense-voice-int8".to_string(),
-                url: Some("https://blob.handy.computer/sense-voice-int8.tar.gz".to_string()),
-                size_mb: 160,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::SenseVoice,
-                accuracy_score: 0.65,
-                speed_score: 0.95,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: sense_voice_languages,
-                is_custom: false,
-            },
-        );
-
-        // Auto-discover custom Whisper models (.bin files) in the models directory
-        if let Err(e) = Self::discover_custom_whisper_models(&models_dir, &mut available_models) {
-            warn!("Failed to discover custom models: {}", e);
-        }
+        // Auto-discover custom models (.bin files and descriptor directories)
+        let roots: Vec<PathBuf> = std::iter::once(models_dir.clone())
+            .chain(search_paths.iter().cloned())
+            .collect();
+        let discovery_errors = Self::discover_in_roots(&roots, &mut available_models);
 
         let manager = Self {
             app_handle: app_handle.clone(),
-            models_dir,
+            models_dir: RwLock::new(models_dir),
+            search_paths: RwLock::new(search_paths),
             available_models: Mutex::new(available_models),
             cancel_flags: Arc::new(Mutex::new(HashMap::new())),
             extracting_models: Arc::new(Mutex::new(HashSet::new())),
+            discovery_errors: Mutex::new(discovery_errors),
+            load_errors: Mutex::new(HashMap::new()),
         };
 
         // Migrate any bundled models to user directory
@@ -422,6 +130,68 @@ impl ModelManager {
         Ok(manager)
     }
 
+    pub fn default_models_dir(app_handle: &AppHandle<R>) -> Result<PathBuf> {
+        Ok(app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
+            .join("models"))
+    }
+
+    pub fn models_dir(&self) -> PathBuf {
+        self.models_dir.read().unwrap().clone()
+    }
+
+    fn search_paths(&self) -> Vec<PathBuf> {
+        self.search_paths.read().unwrap().clone()
+    }
+
+    /// The models directory followed by the search paths
+    fn roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.models_dir())
+            .chain(self.search_paths())
+            .collect()
+    }
+
+    /// Switches the models directory to `new_dir`, first migrating the
+    /// current models into it, then reloads the model list. If migrating
+    /// fails, the models are put back and the current directory is kept.
+    pub fn set_models_dir(&self, new_dir: PathBuf, migration: ModelsDirMigration) -> Result<()> {
+        let busy = self
+            .available_models
+            .lock()
+            .unwrap()
+            .values()
+            .any(|m| m.is_downloading)
+            || !self.extracting_models.lock().unwrap().is_empty();
+        if busy {
+            return Err(anyhow::anyhow!(
+                "Wait for downloads to finish before changing the models directory"
+            ));
+        }
+
+        let current = self.models_dir();
+        if new_dir == current {
+            return Ok(());
+        }
+        validate_new_dir(&current, &new_dir)?;
+        migrate(&current, &new_dir, migration)?;
+
+        info!("Models directory is now {}", new_dir.display());
+        *self.models_dir.write().unwrap() = new_dir;
+        self.reload_catalog()?;
+        let _ = self
+            .app_handle
+            .emit("models-dir-changed", self.models_dir());
+        Ok(())
+    }
+
+    /// Replaces the read-only search paths and reloads the model list.
+    pub fn set_search_paths(&self, search_paths: Vec<PathBuf>) -> Result<()> {
+        *self.search_paths.write().unwrap() = search_paths;
+        self.reload_catalog()
+    }
+
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
         models.values().cloned().collect()
@@ -432,6 +202,64 @@ impl ModelManager {
         models.get(model_id).cloned()
     }
 
+    /// Rebuilds the model list from the catalog and the models directory,
+    /// keeping the state of downloads in progress.
+    pub fn reload_catalog(&self) -> Result<()> {
+        let mut models = load_catalog(&self.models_dir());
+        let discovery_errors = Self::discover_in_roots(&self.roots(), &mut models);
+        *self.discovery_errors.lock().unwrap() = discovery_errors;
+
+        let downloading: HashSet<String> = {
+            let mut current = self.available_models.lock().unwrap();
+            let downloading = current
+                .values()
+                .filter(|m| m.is_downloading)
+                .map(|m| m.id.clone())
+                .collect();
+            *current = models;
+            downloading
+        };
+
+        self.update_download_status()?;
+        let mut models = self.available_models.lock().unwrap();
+        for id in &downloading {
+            if let Some(model) = models.get_mut(id) {
+                model.is_downloading = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Errors per model: custom models that couldn't be registered (keyed by
+    /// their directory name) and models that last failed to load.
+    pub fn get_model_errors(&self) -> HashMap<String, String> {
+        let mut errors = self.discovery_errors.lock().unwrap().clone();
+        errors.extend(self.load_errors.lock().unwrap().clone());
+        errors
+    }
+
+    /// Records the outcome of loading `model_id`; `None` clears its error.
+    pub fn set_load_error(&self, model_id: &str, error: Option<String>) {
+        let mut load_errors = self.load_errors.lock().unwrap();
+        match error {
+            Some(error) => {
+                load_errors.insert(model_id.to_string(), error);
+            }
+            None => {
+                load_errors.remove(model_id);
+            }
+        }
+    }
+
+    /// Fetches the catalog from `url`, caches it and reloads the model list.
+    /// Returns the number of models in the fetched catalog.
+    pub async fn refresh_catalog(&self, url: &str) -> Result<usize> {
+        let count = refresh_remote_catalog(url, &self.models_dir()).await?;
+        self.reload_catalog()?;
+        let _ = self.app_handle.emit("model-catalog-updated", count);
+        Ok(count)
+    }
+
     fn migrate_bundled_models(&self) -> Result<()> {
         // Check for bundled models and copy them to user directory
         let bundled_models = ["ggml-small.bin"]; // Add other bundled models here if any
@@ -444,7 +272,7 @@ impl ModelManager {
 
             if let Ok(bundled_path) = bundled_path {
                 if bundled_path.exists() {
-                    let user_path = self.models_dir.join(filename);
+                    let user_path = self.models_dir().join(filename);
 
                     // Only copy if user doesn't already have the model
                     if !user_path.exists() {
@@ -460,16 +288,16 @@ impl ModelManager {
     }
 
     fn update_download_status(&self) -> Result<()> {
+        let models_dir = self.models_dir();
+        let search_paths = self.search_paths();
         let mut models = self.available_models.lock().unwrap();
 
         for model in models.values_mut() {
             if model.is_directory {
                 // For directory-based models, check if the directory exists
-                let model_path = self.models_dir.join(&model.filename);
-                let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
-                let extracting_path = self
-                    .models_dir
-                    .join(format!("{}.extracting", &model.filename));
+                let model_path = models_dir.join(&model.filename);
+                let partial_path = models_dir.join(format!("{}.partial", &model.filename));
+                let extracting_path = models_dir.join(format!("{}.extracting", &model.filename));
 
                 // Clean up any leftover .extracting directories from interrupted extractions
                 // But only if this model is NOT currently being extracted
@@ -482,7 +310,8 @@ impl ModelManager {
                     let _ = fs::remove_dir_all(&extracting_path);
                 }
 
-                model.is_downloaded = model_path.exists() && model_path.is_dir();
+                model.is_downloaded = model_path.is_dir()
+                    || find_in(&search_paths, &model.filename).is_some_and(|p| p.is_dir());
                 model.is_downloading = false;
 
                 // Get partial file size if it exists (for the .tar.gz being downloaded)
@@ -493,10 +322,11 @@ impl ModelManager {
                 }
             } else {
                 // For file-based models (existing logic)
-                let model_path = self.models_dir.join(&model.filename);
-                let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
+                let model_path = models_dir.join(&model.filename);
+                let partial_path = models_dir.join(format!("{}.partial", &model.filename));
 
-                model.is_downloaded = model_path.exists();
+                model.is_downloaded =
+                    model_path.exists() || find_in(&search_paths, &model.filename).is_some();
                 model.is_downloading = false;
 
                 // Get partial file size if it exists
@@ -553,20 +383,42 @@ impl ModelManager {
         Ok(())
     }
 
-    /// Discover custom Whisper models (.bin files) in the models directory.
-    /// Skips files that match predefined model filenames.
-    fn discover_custom_whisper_models(
+    /// Discovers custom models in each of `roots`, in order, collecting the
+    /// errors of all of them.
+    fn discover_in_roots(
+        roots: &[PathBuf],
+        available_models: &mut HashMap<String, ModelInfo>,
+    ) -> HashMap<String, String> {
+        let mut errors = HashMap::new();
+        for root in roots {
+            match Self::discover_custom_models(root, available_models) {
+                Ok(found) => errors.extend(found),
+                Err(e) => warn!(
+                    "Failed to discover custom models in {}: {}",
+                    root.display(),
+                    e
+                ),
+            }
+        }
+        errors
+    }
+
+    /// Discover custom models in the models directory: Whisper `.bin` files,
+    /// and directories of any engine described by a `handy-model.json`.
+    /// Skips files and directories that match predefined model filenames.
+    /// Returns the directories whose descriptor is invalid, with the reason.
+    fn discover_custom_models(
         models_dir: &Path,
         available_models: &mut HashMap<String, ModelInfo>,
-    ) -> Result<()> {
+    ) -> Result<HashMap<String, String>> {
+        let mut errors = HashMap::new();
         if !models_dir.exists() {
-            return Ok(());
+            return Ok(errors);
         }
 
-        // Collect filenames of predefined Whisper file-based models to skip
+        // Collect filenames of predefined models to skip
         let predefined_filenames: HashSet<String> = available_models
             .values()
-            .filter(|m| matches!(m.engine_type, EngineType::Whisper) && !m.is_directory)
             .map(|m| m.filename.clone())
             .collect();
 
@@ -582,11 +434,6 @@ impl ModelManager {
 
             let path = entry.path();
 
-            // Only process .bin files (not directories)
-            if !path.is_file() {
-                continue;
-            }
-
             let filename = match path.file_name().and_then(|s| s.to_str()) {
                 Some(name) => name.to_string(),
                 None => continue,
@@ -597,6 +444,39 @@ impl ModelManager {
                 continue;
             }
 
+            // Directories are only custom models if they carry a descriptor
+            if path.is_dir() {
+                if predefined_filenames.contains(&filename)
+                    || !path.join(DESCRIPTOR_FILE_NAME).exists()
+                {
+                    continue;
+                }
+                if available_models.contains_key(&filename) {
+                    warn!(
+                        "Custom model '{}' clashes with an existing model id",
+                        filename
+                    );
+                    errors.insert(filename, "Model id is already in use".to_string());
+                    continue;
+                }
+                match ModelDescriptor::read(&path) {
+                    Ok(descriptor) => {
+                        let info =
+                            descriptor.to_model_info(&filename, total_size(&path) / (1024 * 1024));
+                        info!(
+                            "Discovered custom {:?} model: {} ({})",
+                            info.engine_type, info.id, filename
+                        );
+                        available_models.insert(info.id.clone(), info);
+                    }
+                    Err(e) => {
+                        warn!("Skipping custom model {}: {:#}", filename, e);
+                        errors.insert(filename, format!("{:#}", e));
+                    }
+                }
+                continue;
+            }
+
             // Only process .bin files (Whisper GGML format).
             // This also excludes .partial downloads (e.g., "model.bin.partial").
             // If we add discovery for other formats, add a .partial check before this filter.
@@ -617,19 +497,7 @@ impl ModelManager {
                 continue;
             }
 
-            // Generate display name: replace - and _ with space, capitalize words
-            let display_name = model_id
-                .replace(['-', '_'], " ")
-                .split_whitespace()
-                .map(|word| {
-                    let mut chars = word.chars();
-                    match chars.next() {
-                        None => String::new(),
-                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
+            let display_name = display_name(&model_id);
 
             // Get file size in MB
             let size_mb = match path.metadata() {
@@ -665,11 +533,12 @@ impl ModelManager {
                     is_recommended: false,
                     supported_languages: vec![],
                     is_custom: true,
+                    sha256: None,
                 },
             );
         }
 
-        Ok(())
+        Ok(errors)
     }
 
     pub async fn download_model(&self, model_id: &str) -> Result<()> {
@@ -684,9 +553,9 @@ impl ModelManager {
         let url = model_info
             .url
             .ok_or_else(|| anyhow::anyhow!("No download URL for model"))?;
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
 
         // Don't download if complete version already exists
@@ -776,6 +645,25 @@ impl ModelManager {
             response.content_length().unwrap_or(0)
         };
 
+        // Hash while streaming when there is a checksum to compare against;
+        // a resumed download first hashes the bytes already on disk
+        let mut hasher = match &model_info.sha256 {
+            Some(_) => {
+                let mut hasher = StreamHasher::default();
+                if resume_from > 0 {
+                    if let Err(e) = hasher.update_from_file(&partial_path) {
+                        return Err(self.fail_download(
+                            model_id,
+                            &partial_path,
+                            format!("Failed to hash partial download: {}", e),
+                        ));
+                    }
+                }
+                Some(hasher)
+            }
+            None => None,
+        };
+
         let mut downloaded = resume_from;
         let mut stream = response.bytes_stream();
 
@@ -846,6 +734,9 @@ impl ModelManager {
             })?;
 
             file.write_all(&chunk)?;
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&chunk);
+            }
             downloaded += chunk.len() as u64;
 
             let percentage = if total_size > 0 {
@@ -890,19 +781,31 @@ impl ModelManager {
             let actual_size = partial_path.metadata()?.len();
             if actual_size != total_size {
                 // Download is incomplete/corrupted - delete partial and return error
-                let _ = fs::remove_file(&partial_path);
-                {
-                    let mut models = self.available_models.lock().unwrap();
-                    if let Some(model) = models.get_mut(model_id) {
-                        model.is_downloading = false;
-                    }
-                }
-                return Err(anyhow::anyhow!(
-                    "Download incomplete: expected {} bytes, got {} bytes",
-                    total_size,
-                    actual_size
+                return Err(self.fail_download(
+                    model_id,
+                    &partial_path,
+                    format!(
+                        "Download incomplete: expected {} bytes, got {} bytes",
+                        total_size, actual_size
+                    ),
+                ));
+            }
+        }
+
+        // Verify the checksum before anything is renamed or extracted
+        if let (Some(expected), Some(hasher)) = (&model_info.sha256, hasher) {
+            let actual = hasher.finish();
+            if &actual != expected {
+                return Err(self.fail_download(
+                    model_id,
+                    &partial_path,
+                    format!(
+                        "Checksum mismatch: expected SHA-256 {}, got {}",
+                        expected, actual
+                    ),
                 ));
             }
+            info!("Verified SHA-256 of model {}", model_id);
         }
 
         // Handle directory-based models (extract tar.gz) vs file-based models
@@ -919,9 +822,9 @@ impl ModelManager {
 
             // Use a temporary extraction directory to ensure atomic operations
             let temp_extract_dir = self
-                .models_dir
+                .models_dir()
                 .join(format!("{}.extracting", &model_info.filename));
-            let final_model_dir = self.models_dir.join(&model_info.filename);
+            let final_model_dir = self.models_dir().join(&model_info.filename);
 
             // Clean up any previous incomplete extraction
             if temp_extract_dir.exists() {
@@ -979,6 +882,11 @@ impl ModelManager {
                 fs::rename(&temp_extract_dir, &final_model_dir)?;
             }
 
+            // Record the extracted files so verify_model can check them later
+            if let Err(e) = write_directory_checksums(&final_model_dir) {
+                warn!("Failed to write checksums for model {}: {}", model_id, e);
+            }
+
             info!("Successfully extracted archive for model: {}", model_id);
             // Remove from extracting set
             {
@@ -1022,6 +930,263 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Imports a model from a local `.bin` file, directory or `.tar.gz`
+    /// archive, or from a URL, into the models directory and registers it as
+    /// a custom model. Progress is reported with `model-download-progress`
+    /// events under the new model's id.
+    pub async fn import_model(self: &Arc<Self>, source: &str) -> Result<ModelInfo> {
+        let (path, is_download) = match ImportSource::parse(source) {
+            ImportSource::Path(path) => (path, false),
+            ImportSource::Url(url) => (self.fetch_import(&url).await?, true),
+        };
+
+        // Copying or extracting a large model blocks, keep it off the async runtime
+        let manager = self.clone();
+        let import_path = path.clone();
+        let task =
+            tauri::async_runtime::spawn_blocking(move || manager.import_from_path(&import_path));
+        let result = match task.await {
+            Ok(result) => result,
+            Err(e) => Err(anyhow::anyhow!("Model import failed: {}", e)),
+        };
+
+        if is_download {
+            let _ = fs::remove_file(&path);
+        }
+        result
+    }
+
+    /// Downloads an import source into a hidden directory inside the models
+    /// directory, keeping its file name so its kind can be recognized
+    async fn fetch_import(&self, url: &str) -> Result<PathBuf> {
+        let file_name = file_name_from_url(url)
+            .ok_or_else(|| anyhow::anyhow!("Can't tell the model file name from {}", url))?;
+        let (_, target) = classify(Path::new(&file_name))?;
+        let model_id = target.trim_end_matches(".bin").to_string();
+
+        let download_dir = self.models_dir().join(".imports");
+        fs::create_dir_all(&download_dir)?;
+        let path = download_dir.join(&file_name);
+        info!("Downloading model {} for import from {}", model_id, url);
+
+        let result = async {
+            let response = reqwest::get(url).await?.error_for_status()?;
+            let mut progress =
+                self.progress_reporter(&model_id, response.content_length().unwrap_or(0));
+            let mut file = File::create(&path)?;
+            let mut stream = response.bytes_stream();
+            let mut downloaded = 0;
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk?;
+                file.write_all(&chunk)?;
+                downloaded += chunk.len() as u64;
+                progress(downloaded);
+            }
+            file.flush()?;
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            let _ = fs::remove_file(&path);
+            let _ = self.app_handle.emit(
+                "model-download-failed",
+                &serde_json::json!({
+                    "model_id": model_id,
+                    "error": e.to_string()
+                }),
+            );
+            return Err(e);
+        }
+        Ok(path)
+    }
+
+    fn import_from_path(&self, source: &Path) -> Result<ModelInfo> {
+        if !source.exists() {
+            return Err(anyhow::anyhow!("{} does not exist", source.display()));
+        }
+        let (kind, target) = classify(source)?;
+        let model_id = target.trim_end_matches(".bin").to_string();
+
+        let final_path = self.models_dir().join(&target);
+        let taken = {
+            let models = self.available_models.lock().unwrap();
+            models.contains_key(&model_id) || models.values().any(|m| m.filename == target)
+        };
+        if taken || final_path.exists() {
+            return Err(anyhow::anyhow!(
+                "A model named '{}' already exists",
+                model_id
+            ));
+        }
+
+        info!("Importing model {} from {}", model_id, source.display());
+
+        // Stage under a hidden name so discovery never sees a half-copied model
+        let staging = self.models_dir().join(format!(".{}.importing", target));
+        let remove_staging = || {
+            let _ = fs::remove_dir_all(&staging);
+            let _ = fs::remove_file(&staging);
+        };
+        remove_staging();
+
+        let staged = (|| -> Result<()> {
+            let total = total_size(source);
+            let mut progress = self.progress_reporter(&model_id, total);
+            match kind {
+                ImportKind::WhisperFile => copy_file(source, &staging, &mut progress)?,
+                ImportKind::Directory => copy_dir(source, &staging, &mut progress)?,
+                ImportKind::Archive => extract_archive(source, &staging, &mut progress)?,
+            }
+            progress(total);
+
+            // Check a bundled descriptor, or detect the engine and write one
+            // so the model is found again on the next start
+            if kind != ImportKind::WhisperFile {
+                if staging.join(DESCRIPTOR_FILE_NAME).exists() {
+                    ModelDescriptor::read(&staging)?;
+                } else {
+                    let descriptor = ModelDescriptor::detect(&staging, &model_id)?;
+                    fs::write(
+                        staging.join(DESCRIPTOR_FILE_NAME),
+                        serde_json::to_string_pretty(&descriptor)?,
+                    )?;
+                }
+            }
+
+            fs::rename(&staging, &final_path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = staged {
+            remove_staging();
+            warn!("Failed to import model {}: {:#}", model_id, e);
+            let _ = self.app_handle.emit(
+                "model-download-failed",
+                &serde_json::json!({
+                    "model_id": model_id,
+                    "error": format!("{:#}", e)
+                }),
+            );
+            return Err(e);
+        }
+
+        // Register it the same way as custom models found at startup
+        let model_info = {
+            let mut models = self.available_models.lock().unwrap();
+            let errors = Self::discover_custom_models(&self.models_dir(), &mut models)?;
+            self.discovery_errors.lock().unwrap().extend(errors);
+            models.get(&model_id).cloned()
+        }
+        .ok_or_else(|| anyhow::anyhow!("Imported model {} couldn't be registered", model_id))?;
+
+        info!("Imported model {} ({:?})", model_id, model_info.engine_type);
+        let _ = self.app_handle.emit("model-imported", &model_info);
+        Ok(model_info)
+    }
+
+    /// Emits `model-download-progress` for `model_id`, at most every 100ms
+    /// except for the final update
+    fn progress_reporter(&self, model_id: &str, total: u64) -> impl FnMut(u64) + '_ {
+        let model_id = model_id.to_string();
+        let throttle_duration = Duration::from_millis(100);
+        let mut last_emit: Option<Instant> = None;
+        move |downloaded| {
+            let finished = total > 0 && downloaded >= total;
+            if !finished && matches!(last_emit, Some(t) if t.elapsed() < throttle_duration) {
+                return;
+            }
+            last_emit = Some(Instant::now());
+            let progress = DownloadProgress {
+                model_id: model_id.clone(),
+                downloaded,
+                total,
+                percentage: if total > 0 {
+                    (downloaded as f64 / total as f64) * 100.0
+                } else {
+                    0.0
+                },
+            };
+            let _ = self.app_handle.emit("model-download-progress", &progress);
+        }
+    }
+
+    /// Deletes a failed download, resets its state and emits
+    /// `model-download-failed`. Returns the error for the caller to propagate.
+    fn fail_download(&self, model_id: &str, partial_path: &Path, error: String) -> anyhow::Error {
+        warn!("Download of model {} failed: {}", model_id, error);
+        let _ = fs::remove_file(partial_path);
+        {
+            let mut models = self.available_models.lock().unwrap();
+            if let Some(model) = models.get_mut(model_id) {
+                model.is_downloading = false;
+                model.partial_size = 0;
+            }
+        }
+        {
+            let mut flags = self.cancel_flags.lock().unwrap();
+            flags.remove(model_id);
+        }
+        let _ = self.app_handle.emit(
+            "model-download-failed",
+            &serde_json::json!({
+                "model_id": model_id,
+                "error": error
+            }),
+        );
+        anyhow::anyhow!(error)
+    }
+
+    /// Checks a downloaded model against its catalog SHA-256, or, for
+    /// extracted models, against the checksums written after extraction.
+    pub fn verify_model(&self, model_id: &str) -> Result<ModelVerification> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        let model_path = self.get_model_path(model_id)?;
+
+        let (integrity, message) = if model_info.is_directory {
+            match verify_directory_checksums(&model_path)? {
+                None => (
+                    ModelIntegrity::Unknown,
+                    "No checksums recorded for this model".to_string(),
+                ),
+                Some(bad) if bad.is_empty() => {
+                    (ModelIntegrity::Verified, "All files match".to_string())
+                }
+                Some(bad) => (
+                    ModelIntegrity::Corrupted,
+                    format!("Missing or modified: {}", bad.join(", ")),
+                ),
+            }
+        } else {
+            match &model_info.sha256 {
+                None => (
+                    ModelIntegrity::Unknown,
+                    "No checksum known for this model".to_string(),
+                ),
+                Some(expected) => {
+                    let actual = sha256_file(&model_path)?;
+                    if &actual == expected {
+                        (ModelIntegrity::Verified, "SHA-256 matches".to_string())
+                    } else {
+                        (
+                            ModelIntegrity::Corrupted,
+                            format!("Expected SHA-256 {}, got {}", expected, actual),
+                        )
+                    }
+                }
+            }
+        };
+
+        info!("Verified model {}: {:?}", model_id, integrity);
+        Ok(ModelVerification {
+            model_id: model_id.to_string(),
+            integrity,
+            message,
+        })
+    }
+
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: delete_model called for: {}", model_id);
 
@@ -1035,13 +1200,24 @@ impl ModelManager {
 
         debug!("ModelManager: Found model info: {:?}", model_info);
 
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
         debug!("ModelManager: Model path: {:?}", model_path);
         debug!("ModelManager: Partial path: {:?}", partial_path);
 
+        // Search paths are shared with other tools and never modified
+        if !model_path.exists()
+            && !partial_path.exists()
+            && find_in(&self.search_paths(), &model_info.filename).is_some()
+        {
+            return Err(anyhow::anyhow!(
+                "Model {} is in a read-only search path and can't be deleted",
+                model_id
+            ));
+        }
+
         let mut deleted_something = false;
 
         if model_info.is_directory {
@@ -1060,6 +1236,14 @@ impl ModelManager {
                 info!("Model file deleted successfully");
                 deleted_something = true;
             }
+
+            // Custom Whisper models with a descriptor live in their own directory
+            if let Some(parent) = Path::new(&model_info.filename)
+                .parent()
+                .filter(|p| model_info.is_custom && !p.as_os_str().is_empty())
+            {
+                let _ = fs::remove_dir_all(self.models_dir().join(parent));
+            }
         }
 
         // Delete partial file if it exists (same for both types)
@@ -1109,31 +1293,29 @@ impl ModelManager {
             ));
         }
 
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
 
+        // A complete copy in the models directory wins over the search paths
+        let model_path = if model_path.exists() && !partial_path.exists() {
+            Some(model_path)
+        } else {
+            find_in(&self.search_paths(), &model_info.filename)
+        };
+
         if model_info.is_directory {
             // For directory-based models, ensure the directory exists and is complete
-            if model_path.exists() && model_path.is_dir() && !partial_path.exists() {
-                Ok(model_path)
-            } else {
-                Err(anyhow::anyhow!(
+            match model_path {
+                Some(path) if path.is_dir() => Ok(path),
+                _ => Err(anyhow::anyhow!(
                     "Complete model directory not found: {}",
                     model_id
-                ))
+                )),
             }
         } else {
-            // For file-based models (existing logic)
-            if model_path.exists() && !partial_path.exists() {
-                Ok(model_path)
-            } else {
-                Err(anyhow::anyhow!(
-                    "Complete model file not found: {}",
-                    model_id
-                ))
-            }
+            model_path.ok_or_else(|| anyhow::anyhow!("Complete model file not found: {}", model_id))
         }
     }
 
@@ -1216,11 +1398,12 @@ mod tests {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                sha256: None,
             },
         );
 
         // Discover custom models
-        ModelManager::discover_custom_whisper_models(&models_dir, &mut models).unwrap();
+        <ModelManager>::discover_custom_models(&models_dir, &mut models).unwrap();
 
         // Should have discovered 2 custom models (my-custom-model and whisper_medical_v2)
         assert!(models.contains_key("my-custom-model"));
@@ -1247,6 +1430,40 @@ mod tests {
         assert!(!models.contains_key("some-directory"));
     }
 
+    #[test]
+    fn test_discover_custom_models_from_descriptors() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path().to_path_buf();
+
+        let sense_voice = models_dir.join("sense-voice-cantonese");
+        fs::create_dir(&sense_voice).unwrap();
+        fs::write(sense_voice.join("model.int8.onnx"), b"fake model data").unwrap();
+        fs::write(
+            sense_voice.join(DESCRIPTOR_FILE_NAME),
+            r#"{"name": "SenseVoice Cantonese", "engine_type": "SenseVoice",
+                "supported_languages": ["yue"]}"#,
+        )
+        .unwrap();
+
+        // One broken descriptor must not stop the others from registering
+        let broken = models_dir.join("broken-parakeet");
+        fs::create_dir(&broken).unwrap();
+        fs::write(broken.join(DESCRIPTOR_FILE_NAME), "{ not json").unwrap();
+
+        let mut models = HashMap::new();
+        let errors = <ModelManager>::discover_custom_models(&models_dir, &mut models).unwrap();
+
+        let custom = models.get("sense-voice-cantonese").unwrap();
+        assert!(matches!(custom.engine_type, EngineType::SenseVoice));
+        assert!(custom.is_directory);
+        assert!(custom.is_custom);
+        assert_eq!(custom.supported_languages, vec!["yue"]);
+
+        assert!(!models.contains_key("broken-parakeet"));
+        assert!(errors["broken-parakeet"].contains(DESCRIPTOR_FILE_NAME));
+        assert_eq!(errors.len(), 1);
+    }
+
     #[test]
     fn test_discover_custom_models_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -1255,7 +1472,7 @@ mod tests {
         let mut models = HashMap::new();
         let count_before = models.len();
 
-        ModelManager::discover_custom_whisper_models(&models_dir, &mut models).unwrap();
+        <ModelManager>::discover_custom_models(&models_dir, &mut models).unwrap();
 
         // No new models should be added
         assert_eq!(models.len(), count_before);
@@ -1269,7 +1486,7 @@ mod tests {
         let count_before = models.len();
 
         // Should not error, just return Ok
-        let result = ModelManager::discover_custom_whisper_models(&models_dir, &mut models);
+        let result = <ModelManager>::discover_custom_models(&models_dir, &mut models);
         assert!(result.is_ok());
         assert_eq!(models.len(), count_before);
     }
@@ -0,0 +1,336 @@
+//! The catalog of downloadable models, read from a versioned JSON manifest.
+//!
+//! The manifest shipped with the app (`resources/models.json`) is compiled in
+//! so there is always a catalog. A newer copy can be fetched from a remote
+//! URL and is cached in the models directory, and a `models.json` placed in
+//! the models directory overrides or adds entries by id.
+
+use crate::managers::model::{EngineType, ModelInfo};
+use crate::managers::model_integrity::normalize_sha256;
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Manifest format understood by this version of the app
+pub const CATALOG_VERSION: u32 = 1;
+/// Local override file, looked up in the models directory
+pub const OVERRIDE_FILE_NAME: &str = "models.json";
+/// Last catalog fetched from the remote URL, kept in the models directory
+pub const REMOTE_CACHE_FILE_NAME: &str = ".catalog-cache.json";
+
+const BUNDLED_CATALOG: &str = include_str!("../../resources/models.json");
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelCatalog {
+    /// Manifest format, see [`CATALOG_VERSION`]
+    pub version: u32,
+    /// Content revision; a remote catalog only replaces the bundled one when
+    /// its revision is at least as high
+    #[serde(default)]
+    pub revision: u64,
+    /// Named language lists that models can refer to
+    #[serde(default)]
+    pub language_sets: HashMap<String, Vec<String>>,
+    pub models: Vec<CatalogModel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogModel {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// File, or directory for `is_directory` models, inside the models dir
+    pub filename: String,
+    pub url: Option<String>,
+    pub size_mb: u64,
+    #[serde(default)]
+    pub is_directory: bool,
+    pub engine_type: EngineType,
+    pub accuracy_score: f32,
+    pub speed_score: f32,
+    #[serde(default)]
+    pub supports_translation: bool,
+    #[serde(default)]
+    pub is_recommended: bool,
+    pub supported_languages: Languages,
+    /// SHA-256 of the file at `url`; for archives, of the archive itself
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+/// Either a list of language codes or the name of a `language_sets` entry
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Languages {
+    Set(String),
+    List(Vec<String>),
+}
+
+impl ModelCatalog {
+    pub fn parse(json: &str) -> Result<Self> {
+        let catalog: Self = serde_json::from_str(json).context("Invalid model catalog")?;
+        if catalog.version != CATALOG_VERSION {
+            bail!(
+                "Unsupported model catalog version {} (expected {})",
+                catalog.version,
+                CATALOG_VERSION
+            );
+        }
+        Ok(catalog)
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CATALOG).expect("bundled model catalog is invalid")
+    }
+
+    /// Validates every entry and turns the catalog into `ModelInfo`s keyed by id.
+    pub fn resolve(&self) -> Result<HashMap<String, ModelInfo>> {
+        let mut models = HashMap::new();
+        let mut filenames = HashSet::new();
+
+        for model in &self.models {
+            let info = self
+                .resolve_model(model)
+                .with_context(|| format!("Invalid catalog entry '{}'", model.id))?;
+            if !filenames.insert(info.filename.clone()) {
+                bail!("Catalog entries share the filename '{}'", info.filename);
+            }
+            if models.insert(info.id.clone(), info).is_some() {
+                bail!("Duplicate catalog entry '{}'", model.id);
+            }
+        }
+
+        Ok(models)
+    }
+
+    fn resolve_model(&self, model: &CatalogModel) -> Result<ModelInfo> {
+        if model.id.trim().is_empty() || model.name.trim().is_empty() {
+            bail!("id and name must not be empty");
+        }
+        if !is_plain_file_name(&model.filename) {
+            bail!("filename must be a plain file name inside the models directory");
+        }
+        if let Some(url) = &model.url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                bail!("url must be http(s)");
+            }
+        }
+        for score in [model.accuracy_score, model.speed_score] {
+            if !(0.0..=1.0).contains(&score) {
+                bail!("scores must be between 0 and 1");
+            }
+        }
+
+        let sha256 = match &model.sha256 {
+            Some(hash) => Some(normalize_sha256(hash).context("sha256 must be 64 hex digits")?),
+            None => None,
+        };
+
+        let supported_languages = match &model.supported_languages {
+            Languages::List(languages) => languages.clone(),
+            Languages::Set(name) => self
+                .language_sets
+                .get(name)
+                .cloned()
+                .with_context(|| format!("unknown language set '{}'", name))?,
+        };
+
+        Ok(ModelInfo {
+            id: model.id.clone(),
+            name: model.name.clone(),
+            description: model.description.clone(),
+            filename: model.filename.clone(),
+            url: model.url.clone(),
+            size_mb: model.size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: model.is_directory,
+            engine_type: model.engine_type.clone(),
+            accuracy_score: model.accuracy_score,
+            speed_score: model.speed_score,
+            supports_translation: model.supports_translation,
+            is_recommended: model.is_recommended,
+            supported_languages,
+            is_custom: false,
+            sha256,
+        })
+    }
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\'])
+        && Path::new(name).file_name().is_some()
+}
+
+/// Builds the model list for `models_dir`: the remote cache if it is valid
+/// and not older than the bundled catalog, otherwise the bundled catalog,
+/// with entries from the local override file replacing or adding to it.
+/// Invalid files are logged and skipped.
+pub fn load_catalog(models_dir: &Path) -> HashMap<String, ModelInfo> {
+    let bundled = ModelCatalog::bundled();
+    let mut models = bundled.resolve().expect("bundled model catalog is invalid");
+
+    let cache_path = models_dir.join(REMOTE_CACHE_FILE_NAME);
+    if cache_path.exists() {
+        match read_catalog(&cache_path) {
+            Ok((catalog, resolved)) if catalog.revision >= bundled.revision => {
+                info!("Using model catalog revision {}", catalog.revision);
+                models = resolved;
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Ignoring cached model catalog: {:#}", e),
+        }
+    }
+
+    let override_path = models_dir.join(OVERRIDE_FILE_NAME);
+    if override_path.exists() {
+        match read_catalog(&override_path) {
+            Ok((_, overrides)) => {
+                info!(
+                    "Applying {} model catalog overrides from {}",
+                    overrides.len(),
+                    override_path.display()
+                );
+                models.extend(overrides);
+            }
+            Err(e) => warn!("Ignoring model catalog override: {:#}", e),
+        }
+    }
+
+    models
+}
+
+fn read_catalog(path: &Path) -> Result<(ModelCatalog, HashMap<String, ModelInfo>)> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let catalog = ModelCatalog::parse(&json)?;
+    let models = catalog.resolve()?;
+    Ok((catalog, models))
+}
+
+/// Downloads a catalog from `url` and, if it is valid, stores it as the
+/// remote cache in `models_dir`. Returns the number of models it lists.
+pub async fn refresh_remote_catalog(url: &str, models_dir: &Path) -> Result<usize> {
+    let json = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to fetch model catalog from {}", url))?
+        .text()
+        .await?;
+
+    let catalog = ModelCatalog::parse(&json)?;
+    let count = catalog.resolve()?.len();
+    fs::write(models_dir.join(REMOTE_CACHE_FILE_NAME), json)?;
+    info!(
+        "Fetched model catalog revision {} with {} models",
+        catalog.revision, count
+    );
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(models: &str) -> String {
+        format!(
+            r#"{{"version": 1, "revision": 2, "language_sets": {{"eu": ["de", "fr"]}}, "models": [{}]}}"#,
+            models
+        )
+    }
+
+    const MODEL: &str = r#"{"id": "tiny", "name": "Tiny", "description": "", "filename": "tiny.bin",
+        "url": "https://example.com/tiny.bin", "size_mb": 40, "engine_type": "Whisper",
+        "accuracy_score": 0.4, "speed_score": 0.9, "supported_languages": "eu"}"#;
+
+    #[test]
+    fn bundled_catalog_is_valid() {
+        let models = ModelCatalog::bundled().resolve().unwrap();
+        assert!(models.contains_key("parakeet-tdt-0.6b-v3"));
+        assert!(models.values().any(|m| m.is_recommended));
+        assert!(models["small"].supported_languages.len() > 90);
+    }
+
+    #[test]
+    #[ignore = "run `bun run update:model-checksums` to record the published hashes"]
+    fn downloadable_models_have_checksums() {
+        let models = ModelCatalog::bundled().resolve().unwrap();
+        let missing: Vec<&str> = models
+            .values()
+            .filter(|m| m.url.is_some() && m.sha256.is_none())
+            .map(|m| m.id.as_str())
+            .collect();
+        assert!(missing.is_empty(), "models without sha256: {:?}", missing);
+    }
+
+    #[test]
+    fn resolves_language_sets() {
+        let models = ModelCatalog::parse(&catalog(MODEL))
+            .unwrap()
+            .resolve()
+            .unwrap();
+        let tiny = &models["tiny"];
+        assert_eq!(tiny.supported_languages, vec!["de", "fr"]);
+        assert!(!tiny.is_directory);
+        assert!(!tiny.is_custom);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let invalid = [
+            MODEL.replace("\"eu\"", "\"missing\""),
+            MODEL.replace("tiny.bin\",", "../tiny.bin\","),
+            MODEL.replace("0.4", "1.5"),
+            MODEL.replace("Whisper", "Wav2Vec"),
+            MODEL.replace("\"size_mb\"", "\"sha256\": \"abc\", \"size_mb\""),
+            format!("{},{}", MODEL, MODEL),
+        ];
+        for models in invalid {
+            let result = ModelCatalog::parse(&catalog(&models)).and_then(|c| c.resolve());
+            assert!(result.is_err(), "accepted {}", models);
+        }
+
+        let future = catalog(MODEL).replace("\"version\": 1", "\"version\": 99");
+        assert!(ModelCatalog::parse(&future).is_err());
+    }
+
+    #[test]
+    fn override_file_replaces_and_adds_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let replaced = MODEL
+            .replace("\"tiny\"", "\"small\"")
+            .replace("Tiny", "My Small")
+            .replace("tiny.bin", "my-small.bin");
+        fs::write(
+            dir.path().join(OVERRIDE_FILE_NAME),
+            catalog(&format!("{},{}", MODEL, replaced)),
+        )
+        .unwrap();
+
+        let models = load_catalog(dir.path());
+        assert_eq!(models["small"].name, "My Small");
+        assert!(models.contains_key("tiny"));
+        assert!(models.contains_key("parakeet-tdt-0.6b-v3"));
+    }
+
+    #[test]
+    fn invalid_cache_falls_back_to_bundled() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(REMOTE_CACHE_FILE_NAME), "{ not json").unwrap();
+
+        let models = load_catalog(dir.path());
+        assert_eq!(
+            models.len(),
+            ModelCatalog::bundled().resolve().unwrap().len()
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Descriptors for custom models of any engine.
+//!
+//! A directory in the models directory that contains a `handy-model.json` is
+//! registered as a custom model. The descriptor says which engine loads it;
+//! for Whisper it also names the GGML file inside the directory, since that
+//! engine loads a single file rather than a directory.
+
+use crate::managers::model::{EngineType, ModelInfo};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const DESCRIPTOR_FILE_NAME: &str = "handy-model.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelDescriptor {
+    pub name: String,
+    pub engine_type: EngineType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub supported_languages: Vec<String>,
+    #[serde(default)]
+    pub supports_translation: bool,
+    /// Model file inside the directory; required for Whisper
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_file: Option<String>,
+}
+
+impl ModelDescriptor {
+    /// Reads and validates the descriptor in `dir`
+    pub fn read(dir: &Path) -> Result<Self> {
+        let path = dir.join(DESCRIPTOR_FILE_NAME);
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let descriptor: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid {}", DESCRIPTOR_FILE_NAME))?;
+        descriptor.validate(dir)?;
+        Ok(descriptor)
+    }
+
+    fn validate(&self, dir: &Path) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("name must not be empty");
+        }
+
+        match (&self.engine_type, &self.model_file) {
+            (EngineType::Whisper, None) => {
+                bail!("model_file is required for Whisper models")
+            }
+            (_, Some(file)) => {
+                if file.contains(['/', '\\']) || file.starts_with('.') {
+                    bail!("model_file must be a file name inside the model directory");
+                }
+                if !dir.join(file).is_file() {
+                    bail!("model_file '{}' not found", file);
+                }
+            }
+            (_, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Guesses the engine from the files in `dir`, for models imported
+    /// without a descriptor. Only layouts matching the official models are
+    /// recognized; anything else needs a descriptor.
+    pub fn detect(dir: &Path, model_id: &str) -> Result<Self> {
+        let files: Vec<String> = fs::read_dir(dir)?
+            .flatten()
+            .filter(|e| e.path().is_file())
+            .filter_map(|e| e.file_name().to_str().map(str::to_string))
+            .collect();
+        let has = |predicate: fn(&str) -> bool| files.iter().any(|f| predicate(f));
+
+        let bin_files: Vec<&String> = files.iter().filter(|f| f.ends_with(".bin")).collect();
+        let (engine_type, model_file) =
+            if has(|f| f == "nemo128.onnx") || has(|f| f.starts_with("decoder_joint-model")) {
+                (EngineType::Parakeet, None)
+            } else if has(|f| f.starts_with("decoder_model_merged")) {
+                (EngineType::Moonshine, None)
+            } else if has(|f| f == "tokens.txt")
+                && has(|f| f.starts_with("model") && f.ends_with(".onnx"))
+            {
+                (EngineType::SenseVoice, None)
+            } else if let [bin] = bin_files.as_slice() {
+                (EngineType::Whisper, Some(bin.to_string()))
+            } else {
+                bail!(
+                    "Couldn't detect the model's engine; add a {} describing it",
+                    DESCRIPTOR_FILE_NAME
+                );
+            };
+
+        Ok(Self {
+            name: display_name(model_id),
+            engine_type,
+            description: None,
+            supported_languages: vec![],
+            supports_translation: false,
+            model_file,
+        })
+    }
+
+    /// Builds the custom model entry for the directory `dir_name`, which
+    /// also becomes the model id
+    pub fn to_model_info(&self, dir_name: &str, size_mb: u64) -> ModelInfo {
+        // Whisper loads a single file, every other engine the whole directory
+        let (filename, is_directory) = match (&self.engine_type, &self.model_file) {
+            (EngineType::Whisper, Some(file)) => (format!("{}/{}", dir_name, file), false),
+            _ => (dir_name.to_string(), true),
+        };
+
+        ModelInfo {
+            id: dir_name.to_string(),
+            name: self.name.trim().to_string(),
+            description: self
+                .description
+                .clone()
+                .unwrap_or_else(|| "Not officially supported".to_string()),
+            filename,
+            url: None,
+            size_mb,
+            is_downloaded: true,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory,
+            engine_type: self.engine_type.clone(),
+            accuracy_score: 0.0, // Sentinel: UI hides score bars when both are 0
+            speed_score: 0.0,
+            supports_translation: self.supports_translation,
+            is_recommended: false,
+            supported_languages: self.supported_languages.clone(),
+            is_custom: true,
+            sha256: None,
+        }
+    }
+}
+
+/// Display name for a model id: `-` and `_` become spaces, words are capitalized
+pub fn display_name(model_id: &str) -> String {
+    model_id
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_descriptor(dir: &Path, json: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(DESCRIPTOR_FILE_NAME), json).unwrap();
+    }
+
+    #[test]
+    fn directory_models_use_the_whole_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("parakeet-medical");
+        write_descriptor(
+            &dir,
+            r#"{"name": "Parakeet Medical", "engine_type": "Parakeet",
+                "supported_languages": ["en", "de"]}"#,
+        );
+
+        let info = ModelDescriptor::read(&dir)
+            .unwrap()
+            .to_model_info("parakeet-medical", 0);
+        assert_eq!(info.id, "parakeet-medical");
+        assert_eq!(info.filename, "parakeet-medical");
+        assert!(info.is_directory);
+        assert!(info.is_custom);
+        assert!(info.is_downloaded);
+        assert_eq!(info.supported_languages, vec!["en", "de"]);
+    }
+
+    #[test]
+    fn whisper_models_point_at_their_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("whisper-legal");
+        write_descriptor(
+            &dir,
+            r#"{"name": "Whisper Legal", "engine_type": "Whisper",
+                "model_file": "ggml-legal.bin", "supports_translation": true}"#,
+        );
+        assert!(ModelDescriptor::read(&dir).is_err());
+
+        fs::write(dir.join("ggml-legal.bin"), b"weights").unwrap();
+        let info = ModelDescriptor::read(&dir)
+            .unwrap()
+            .to_model_info("whisper-legal", 0);
+        assert_eq!(info.filename, "whisper-legal/ggml-legal.bin");
+        assert!(!info.is_directory);
+        assert!(info.supports_translation);
+    }
+
+    #[test]
+    fn detects_engines_from_official_layouts() {
+        let temp = tempfile::tempdir().unwrap();
+        let layouts: [(&[&str], EngineType); 4] = [
+            (
+                &[
+                    "encoder-model.int8.onnx",
+                    "decoder_joint-model.int8.onnx",
+                    "nemo128.onnx",
+                    "vocab.txt",
+                ],
+                EngineType::Parakeet,
+            ),
+            (
+                &[
+                    "encoder_model.onnx",
+                    "decoder_model_merged.onnx",
+                    "tokenizer.json",
+                ],
+                EngineType::Moonshine,
+            ),
+            (&["model.int8.onnx", "tokens.txt"], EngineType::SenseVoice),
+            (&["ggml-custom.bin"], EngineType::Whisper),
+        ];
+
+        for (i, (files, engine_type)) in layouts.iter().enumerate() {
+            let dir = temp.path().join(i.to_string());
+            fs::create_dir(&dir).unwrap();
+            for file in *files {
+                fs::write(dir.join(file), b"").unwrap();
+            }
+            let descriptor = ModelDescriptor::detect(&dir, "my_model").unwrap();
+            assert_eq!(
+                std::mem::discriminant(&descriptor.engine_type),
+                std::mem::discriminant(engine_type)
+            );
+            assert_eq!(descriptor.name, "My Model");
+        }
+
+        let unknown = temp.path().join("unknown");
+        fs::create_dir(&unknown).unwrap();
+        fs::write(unknown.join("weights.safetensors"), b"").unwrap();
+        assert!(ModelDescriptor::detect(&unknown, "unknown").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_descriptors() {
+        let temp = tempfile::tempdir().unwrap();
+        let invalid = [
+            "{ not json",
+            r#"{"name": "", "engine_type": "Parakeet"}"#,
+            r#"{"name": "No Engine"}"#,
+            r#"{"name": "Unknown", "engine_type": "Wav2Vec"}"#,
+            r#"{"name": "Whisper", "engine_type": "Whisper"}"#,
+            r#"{"name": "Escape", "engine_type": "Whisper", "model_file": "../ggml.bin"}"#,
+            r#"{"name": "Typo", "engine_type": "SenseVoice", "languages": ["zh"]}"#,
+        ];
+        for (i, json) in invalid.iter().enumerate() {
+            let dir = temp.path().join(i.to_string());
+            write_descriptor(&dir, json);
+            assert!(ModelDescriptor::read(&dir).is_err(), "accepted {}", json);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Copying and extracting models imported from outside the models directory.
+//!
+//! An import source is a Whisper `.bin` file, a model directory, or a
+//! `.tar.gz` archive of one, given as a local path or an http(s) URL. The
+//! helpers here report progress in bytes so the caller can reuse the
+//! download progress events.
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tar::Archive;
+
+const ARCHIVE_EXTENSIONS: [&str; 2] = [".tar.gz", ".tgz"];
+
+#[derive(Debug, PartialEq)]
+pub enum ImportSource {
+    Url(String),
+    Path(PathBuf),
+}
+
+impl ImportSource {
+    pub fn parse(source: &str) -> Self {
+        let source = source.trim();
+        if source.starts_with("https://") || source.starts_with("http://") {
+            Self::Url(source.to_string())
+        } else {
+            Self::Path(PathBuf::from(source))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportKind {
+    /// A single Whisper GGML file
+    WhisperFile,
+    Directory,
+    Archive,
+}
+
+/// File name of the last segment of `url`, ignoring any query string
+pub fn file_name_from_url(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.trim_end_matches('/').rsplit('/').next()?;
+    (!name.is_empty() && !name.contains(':')).then(|| name.to_string())
+}
+
+/// What `path` holds and the name it gets in the models directory: the file
+/// name for `.bin` files, the directory name, or the archive name without
+/// its extension
+pub fn classify(path: &Path) -> Result<(ImportKind, String)> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("Invalid model path {}", path.display()))?;
+
+    let (kind, target) = if path.is_dir() {
+        (ImportKind::Directory, name.to_string())
+    } else if let Some(stem) = ARCHIVE_EXTENSIONS
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+    {
+        (ImportKind::Archive, stem.to_string())
+    } else if name.ends_with(".bin") {
+        (ImportKind::WhisperFile, name.to_string())
+    } else {
+        bail!(
+            "Unsupported model '{}': expected a .bin file, a directory or a .tar.gz archive",
+            name
+        );
+    };
+
+    if target.trim_end_matches(".bin").is_empty() || target.starts_with('.') {
+        bail!("Invalid model name '{}'", target);
+    }
+    Ok((kind, target))
+}
+
+/// Reader that reports the number of bytes read so far
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        (self.on_progress)(self.read);
+        Ok(n)
+    }
+}
+
+/// Total size of the files under `path`, or of `path` itself
+pub fn total_size(path: &Path) -> u64 {
+    match fs::metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| total_size(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Copies `src` to `dst`, calling `on_progress` with the bytes copied so far
+/// across all files
+pub fn copy_file(src: &Path, dst: &Path, on_progress: &mut impl FnMut(u64)) -> Result<()> {
+    copy_file_from(src, dst, 0, on_progress).map(|_| ())
+}
+
+fn copy_file_from(
+    src: &Path,
+    dst: &Path,
+    offset: u64,
+    on_progress: &mut impl FnMut(u64),
+) -> Result<u64> {
+    let mut reader = ProgressReader {
+        inner: File::open(src).with_context(|| format!("Failed to open {}", src.display()))?,
+        read: 0,
+        on_progress: |read| on_progress(offset + read),
+    };
+    let mut writer =
+        File::create(dst).with_context(|| format!("Failed to create {}", dst.display()))?;
+    let copied = io::copy(&mut reader, &mut writer)?;
+    writer.flush()?;
+    Ok(offset + copied)
+}
+
+/// Recursively copies the directory `src` to `dst`
+pub fn copy_dir(src: &Path, dst: &Path, on_progress: &mut impl FnMut(u64)) -> Result<()> {
+    fn copy(src: &Path, dst: &Path, copied: u64, on_progress: &mut impl FnMut(u64)) -> Result<u64> {
+        fs::create_dir_all(dst)?;
+        let mut copied = copied;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let target = dst.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                copied = copy(&entry.path(), &target, copied, on_progress)?;
+            } else {
+                copied = copy_file_from(&entry.path(), &target, copied, on_progress)?;
+            }
+        }
+        Ok(copied)
+    }
+    copy(src, dst, 0, on_progress).map(|_| ())
+}
+
+/// Extracts the archive `src` into `dst`. Progress counts compressed bytes.
+/// An archive holding a single top-level directory is unwrapped so the
+/// model files end up directly in `dst`.
+pub fn extract_archive(src: &Path, dst: &Path, on_progress: &mut impl FnMut(u64)) -> Result<()> {
+    let reader = ProgressReader {
+        inner: File::open(src).with_context(|| format!("Failed to open {}", src.display()))?,
+        read: 0,
+        on_progress,
+    };
+    let unpack_dir = dst.with_extension("unpack");
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+    fs::create_dir_all(&unpack_dir)?;
+
+    let result = Archive::new(GzDecoder::new(reader))
+        .unpack(&unpack_dir)
+        .context("Failed to extract archive")
+        .and_then(|_| {
+            let entries: Vec<_> = fs::read_dir(&unpack_dir)?.flatten().collect();
+            let root = match entries.as_slice() {
+                [single] if single.path().is_dir() => single.path(),
+                _ => unpack_dir.clone(),
+            };
+            fs::rename(&root, dst)?;
+            Ok(())
+        });
+
+    let _ = fs::remove_dir_all(&unpack_dir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(
+            ImportSource::parse(" https://example.com/m.tar.gz "),
+            ImportSource::Url("https://example.com/m.tar.gz".to_string())
+        );
+        assert_eq!(
+            ImportSource::parse("/media/usb/ggml-legal.bin"),
+            ImportSource::Path(PathBuf::from("/media/usb/ggml-legal.bin"))
+        );
+        assert_eq!(
+            file_name_from_url("https://example.com/models/sense-voice.tar.gz?download=1"),
+            Some("sense-voice.tar.gz".to_string())
+        );
+        assert_eq!(file_name_from_url("https://"), None);
+    }
+
+    #[test]
+    fn classifies_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("parakeet-medical");
+        fs::create_dir(&dir).unwrap();
+        let bin = temp.path().join("ggml-legal.bin");
+        fs::write(&bin, b"").unwrap();
+
+        assert_eq!(
+            classify(&dir).unwrap(),
+            (ImportKind::Directory, "parakeet-medical".to_string())
+        );
+        assert_eq!(
+            classify(&bin).unwrap(),
+            (ImportKind::WhisperFile, "ggml-legal.bin".to_string())
+        );
+        assert_eq!(
+            classify(Path::new("/tmp/sense-voice.tar.gz")).unwrap(),
+            (ImportKind::Archive, "sense-voice".to_string())
+        );
+        assert!(classify(Path::new("/tmp/model.safetensors")).is_err());
+        assert!(classify(Path::new("/tmp/.tar.gz")).is_err());
+    }
+
+    #[test]
+    fn copies_directories_with_progress() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("model.onnx"), b"12345").unwrap();
+        fs::write(src.join("nested/tokens.txt"), b"678").unwrap();
+
+        let dst = temp.path().join("dst");
+        let mut last = 0;
+        copy_dir(&src, &dst, &mut |copied| last = copied).unwrap();
+
+        assert_eq!(last, total_size(&src));
+        assert_eq!(fs::read(dst.join("nested/tokens.txt")).unwrap(), b"678");
+    }
+
+    #[test]
+    fn extracts_archives_without_the_top_level_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive_path = temp.path().join("model.tar.gz");
+        {
+            let mut builder = tar::Builder::new(GzEncoder::new(
+                File::create(&archive_path).unwrap(),
+                Compression::default(),
+            ));
+            let mut header = tar::Header::new_gnu();
+            header.set_size(5);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "sense-voice/model.int8.onnx", &b"12345"[..])
+                .unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dst = temp.path().join("sense-voice");
+        let mut last = 0;
+        extract_archive(&archive_path, &dst, &mut |read| last = read).unwrap();
+
+        assert_eq!(fs::read(dst.join("model.int8.onnx")).unwrap(), b"12345");
+        assert!(last > 0);
+        assert!(!dst.with_extension("unpack").exists());
+    }
+}
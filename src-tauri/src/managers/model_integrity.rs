@@ -0,0 +1,200 @@
+//! SHA-256 checks for model files.
+//!
+//! File-based models are compared against the `sha256` from the catalog.
+//! Archives are checked before extraction; afterwards the extracted files are
+//! listed with their hashes in a checksum file so the directory can be
+//! verified later without the archive.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Written inside extracted model directories, in `sha256sum` format
+pub const CHECKSUM_FILE_NAME: &str = ".sha256sums";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelIntegrity {
+    /// Every file matches its recorded hash
+    Verified,
+    /// At least one file is missing or doesn't match
+    Corrupted,
+    /// There is no hash to compare against
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct ModelVerification {
+    pub model_id: String,
+    pub integrity: ModelIntegrity,
+    pub message: String,
+}
+
+/// Incremental hasher fed with each downloaded chunk
+#[derive(Default)]
+pub struct StreamHasher(Sha256);
+
+impl StreamHasher {
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    /// Feeds the contents of an existing file, for resumed downloads
+    pub fn update_from_file(&mut self, path: &Path) -> Result<()> {
+        hash_reader(&mut File::open(path)?, &mut self.0)
+    }
+
+    pub fn finish(self) -> String {
+        hex(&self.0.finalize())
+    }
+}
+
+/// Lowercase hex SHA-256 if `value` is one, for validating catalog entries
+pub fn normalize_sha256(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    hash_reader(&mut file, &mut hasher)?;
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hash_reader(reader: &mut impl Read, hasher: &mut Sha256) -> Result<()> {
+    let mut buf = vec![0; 1024 * 1024];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            return Ok(());
+        }
+        hasher.update(&buf[..read]);
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hashes every file under `dir` and writes the checksum file
+pub fn write_directory_checksums(dir: &Path) -> Result<()> {
+    let mut lines = String::new();
+    for relative in list_files(dir)? {
+        let hash = sha256_file(&dir.join(&relative))?;
+        lines.push_str(&format!("{}  {}\n", hash, to_slash(&relative)));
+    }
+    fs::write(dir.join(CHECKSUM_FILE_NAME), lines)?;
+    Ok(())
+}
+
+/// Compares the files under `dir` with its checksum file. Returns the files
+/// that are missing or differ, or `None` if there is no checksum file.
+pub fn verify_directory_checksums(dir: &Path) -> Result<Option<Vec<String>>> {
+    let sums_path = dir.join(CHECKSUM_FILE_NAME);
+    if !sums_path.exists() {
+        return Ok(None);
+    }
+
+    let mut bad = Vec::new();
+    for line in fs::read_to_string(&sums_path)?.lines() {
+        let Some((expected, name)) = line.split_once("  ") else {
+            continue;
+        };
+        let path = dir.join(name);
+        match sha256_file(&path) {
+            Ok(actual) if actual == expected => {}
+            _ => bad.push(name.to_string()),
+        }
+    }
+    Ok(Some(bad))
+}
+
+/// Files under `dir`, relative to it and sorted, without the checksum file
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in fs::read_dir(dir.join(&relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else if path != Path::new(CHECKSUM_FILE_NAME) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sha256("abc")
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn hashes_files_and_streams() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        fs::write(&path, b"ab").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "fb8e20fc2e4c3f248c60c39bd652f3c1347298bb977b8b4d5903b85055620603"
+        );
+
+        // A resumed download hashes the partial file, then the new chunks
+        let mut hasher = StreamHasher::default();
+        hasher.update_from_file(&path).unwrap();
+        hasher.update(b"c");
+        assert_eq!(hasher.finish(), ABC);
+    }
+
+    #[test]
+    fn normalizes_catalog_hashes() {
+        assert_eq!(normalize_sha256(&ABC.to_uppercase()).as_deref(), Some(ABC));
+        assert_eq!(normalize_sha256("abc"), None);
+        assert_eq!(normalize_sha256(&ABC.replace('a', "g")), None);
+    }
+
+    #[test]
+    fn directory_checksums_detect_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(verify_directory_checksums(dir.path()).unwrap(), None);
+
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("encoder.onnx"), b"encoder").unwrap();
+        fs::write(dir.path().join("nested/vocab.txt"), b"vocab").unwrap();
+        write_directory_checksums(dir.path()).unwrap();
+        assert_eq!(
+            verify_directory_checksums(dir.path()).unwrap(),
+            Some(vec![])
+        );
+
+        fs::write(dir.path().join("nested/vocab.txt"), b"changed").unwrap();
+        fs::remove_file(dir.path().join("encoder.onnx")).unwrap();
+        assert_eq!(
+            verify_directory_checksums(dir.path()).unwrap(),
+            Some(vec![
+                "encoder.onnx".to_string(),
+                "nested/vocab.txt".to_string()
+            ])
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Moving the models directory and resolving models across search paths.
+//!
+//! Handy writes models to one directory, by default `models` in the app data
+//! directory. Extra search paths are only read, so a store shared with other
+//! whisper.cpp tools is never modified.
+
+use crate::managers::model_import::{copy_dir, copy_file};
+use anyhow::{bail, Context, Result};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What to do with the models already in the old directory
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelsDirMigration {
+    /// Move everything to the new directory
+    Move,
+    /// Leave the models where they are and link to them from the new directory
+    Symlink,
+    /// Start with whatever is in the new directory
+    None,
+}
+
+/// Leftovers of interrupted downloads, extractions and imports, which are
+/// never migrated
+fn is_transient(name: &str) -> bool {
+    name.ends_with(".partial")
+        || name.ends_with(".extracting")
+        || name.ends_with(".importing")
+        || name == ".imports"
+}
+
+/// First of `roots` containing `filename`
+pub fn find_in(roots: &[PathBuf], filename: &str) -> Option<PathBuf> {
+    roots
+        .iter()
+        .map(|root| root.join(filename))
+        .find(|path| path.exists())
+}
+
+/// Checks that `to` can become the models directory in place of `from`
+pub fn validate_new_dir(from: &Path, to: &Path) -> Result<()> {
+    if !to.is_absolute() {
+        bail!("The models directory must be an absolute path");
+    }
+    if to.exists() && !to.is_dir() {
+        bail!("{} is not a directory", to.display());
+    }
+    if to.starts_with(from) || from.starts_with(to) {
+        bail!("The new models directory can't contain or be inside the current one");
+    }
+    Ok(())
+}
+
+/// Brings the models in `from` into `to` as `migration` says. Entries that
+/// already exist in `to` are left alone. Returns how many were migrated.
+///
+/// If an entry can't be migrated, the ones migrated before it are put back
+/// so `from` stays complete and can remain the models directory.
+pub fn migrate(from: &Path, to: &Path, migration: ModelsDirMigration) -> Result<usize> {
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    if migration == ModelsDirMigration::None || !from.exists() {
+        return Ok(0);
+    }
+
+    let mut sources: Vec<PathBuf> = fs::read_dir(from)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    sources.sort();
+
+    let mut migrated: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for source in sources {
+        let Some(name) = source
+            .file_name()
+            .filter(|n| n.to_str().is_some_and(|n| !is_transient(n)))
+        else {
+            continue;
+        };
+        let target = to.join(name);
+        if target.exists() {
+            warn!(
+                "Not migrating {}: it already exists in {}",
+                source.display(),
+                to.display()
+            );
+            continue;
+        }
+
+        let result = match migration {
+            ModelsDirMigration::Move => move_entry(&source, &target),
+            ModelsDirMigration::Symlink => symlink(&source, &target),
+            ModelsDirMigration::None => unreachable!(),
+        };
+        if let Err(e) = result {
+            let stranded = roll_back(&migrated, migration);
+            if stranded > 0 {
+                return Err(e.context(format!(
+                    "{} migrated entries could not be put back and remain in {}",
+                    stranded,
+                    to.display()
+                )));
+            }
+            return Err(e);
+        }
+        migrated.push((source, target));
+    }
+
+    info!(
+        "Migrated {} entries from {} to {} ({:?})",
+        migrated.len(),
+        from.display(),
+        to.display(),
+        migration
+    );
+    Ok(migrated.len())
+}
+
+/// Undoes the `(source, target)` pairs of an interrupted migration. Returns
+/// how many couldn't be undone.
+fn roll_back(migrated: &[(PathBuf, PathBuf)], migration: ModelsDirMigration) -> usize {
+    let mut stranded = 0;
+    for (source, target) in migrated.iter().rev() {
+        let undone = match migration {
+            ModelsDirMigration::Move => move_entry(target, source),
+            _ => fs::remove_file(target)
+                .or_else(|_| fs::remove_dir(target))
+                .with_context(|| format!("Failed to remove {}", target.display())),
+        };
+        if let Err(e) = undone {
+            error!("Failed to undo migrating {}: {:#}", source.display(), e);
+            stranded += 1;
+        }
+    }
+    stranded
+}
+
+/// Renames `source` to `target`, copying and deleting when they are on
+/// different drives
+fn move_entry(source: &Path, target: &Path) -> Result<()> {
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+
+    let copied = if source.is_dir() {
+        copy_dir(source, target, &mut |_| {})
+    } else {
+        copy_file(source, target, &mut |_| {})
+    };
+    if let Err(e) = copied {
+        let _ = fs::remove_dir_all(target);
+        let _ = fs::remove_file(target);
+        return Err(e.context(format!("Failed to move {}", source.display())));
+    }
+
+    if source.is_dir() {
+        fs::remove_dir_all(source)?;
+    } else {
+        fs::remove_file(source)?;
+    }
+    Ok(())
+}
+
+fn symlink(source: &Path, target: &Path) -> Result<()> {
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(source, target);
+    #[cfg(windows)]
+    let result = if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, target)
+    } else {
+        std::os::windows::fs::symlink_file(source, target)
+    };
+    result.with_context(|| format!("Failed to link {}", source.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn old_dir(root: &Path) -> PathBuf {
+        let dir = root.join("old");
+        fs::create_dir_all(dir.join("parakeet-tdt-0.6b-v3-int8")).unwrap();
+        fs::write(dir.join("parakeet-tdt-0.6b-v3-int8/vocab.txt"), b"vocab").unwrap();
+        fs::write(dir.join("ggml-small.bin"), b"weights").unwrap();
+        fs::write(dir.join("ggml-medium.bin.partial"), b"half").unwrap();
+        dir
+    }
+
+    #[test]
+    fn moves_models_but_not_partial_downloads() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = old_dir(temp.path());
+        let to = temp.path().join("new");
+
+        assert_eq!(migrate(&from, &to, ModelsDirMigration::Move).unwrap(), 2);
+        assert_eq!(fs::read(to.join("ggml-small.bin")).unwrap(), b"weights");
+        assert!(to.join("parakeet-tdt-0.6b-v3-int8/vocab.txt").exists());
+        assert!(!to.join("ggml-medium.bin.partial").exists());
+        assert!(!from.join("ggml-small.bin").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_leave_models_in_place() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = old_dir(temp.path());
+        let to = temp.path().join("new");
+        fs::create_dir_all(&to).unwrap();
+        fs::write(to.join("ggml-small.bin"), b"already here").unwrap();
+
+        assert_eq!(migrate(&from, &to, ModelsDirMigration::Symlink).unwrap(), 1);
+        assert!(from.join("ggml-small.bin").exists());
+        assert_eq!(
+            fs::read(to.join("ggml-small.bin")).unwrap(),
+            b"already here"
+        );
+        assert!(fs::symlink_metadata(to.join("parakeet-tdt-0.6b-v3-int8"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_moves_are_rolled_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = old_dir(temp.path());
+        let to = temp.path().join("new");
+        fs::create_dir_all(&to).unwrap();
+        // A dangling link doesn't count as existing but blocks moving the
+        // directory, after ggml-small.bin has already been moved
+        std::os::unix::fs::symlink(
+            temp.path().join("missing"),
+            to.join("parakeet-tdt-0.6b-v3-int8"),
+        )
+        .unwrap();
+
+        assert!(migrate(&from, &to, ModelsDirMigration::Move).is_err());
+        assert_eq!(fs::read(from.join("ggml-small.bin")).unwrap(), b"weights");
+        assert!(from.join("parakeet-tdt-0.6b-v3-int8/vocab.txt").exists());
+        assert!(!to.join("ggml-small.bin").exists());
+    }
+
+    #[test]
+    fn resolves_across_roots_in_order() {
+        let temp = tempfile::tempdir().unwrap();
+        let primary = temp.path().join("primary");
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(&primary).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        fs::write(shared.join("ggml-small.bin"), b"").unwrap();
+        fs::write(shared.join("ggml-base.bin"), b"").unwrap();
+        fs::write(primary.join("ggml-base.bin"), b"").unwrap();
+
+        let roots = [primary.clone(), shared.clone()];
+        assert_eq!(
+            find_in(&roots, "ggml-small.bin"),
+            Some(shared.join("ggml-small.bin"))
+        );
+        assert_eq!(
+            find_in(&roots, "ggml-base.bin"),
+            Some(primary.join("ggml-base.bin"))
+        );
+        assert_eq!(find_in(&roots, "ggml-large.bin"), None);
+    }
+
+    #[test]
+    fn rejects_nested_or_relative_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = temp.path().join("models");
+        assert!(validate_new_dir(&from, &from.join("inner")).is_err());
+        assert!(validate_new_dir(&from, temp.path()).is_err());
+        assert!(validate_new_dir(&from, Path::new("relative/models")).is_err());
+        assert!(validate_new_dir(&from, &temp.path().join("elsewhere")).is_ok());
+    }
+}
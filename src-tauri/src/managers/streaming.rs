@@ -0,0 +1,142 @@
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::managers::history::TranscriptionOutput;
+use crate::managers::transcription::TranscriptionManager;
+use log::{debug, warn};
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+/// Text recognised so far in the current recording
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct PartialTranscriptionEvent {
+    pub text: String,
+}
+
+struct StreamingSession {
+    worker: thread::JoinHandle<Option<TranscriptionOutput>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Transcribes speech segments while the user is still recording.
+///
+/// Each segment the recorder delivers is run through the engine as soon as
+/// it ends, and the text so far is emitted as a `partial-transcription`
+/// event. When recording stops only the last segment is left to transcribe.
+pub struct StreamingTranscriptionManager {
+    app_handle: AppHandle,
+    transcription_manager: Arc<TranscriptionManager>,
+    session: Mutex<Option<StreamingSession>>,
+}
+
+impl StreamingTranscriptionManager {
+    pub fn new(app_handle: &AppHandle, transcription_manager: Arc<TranscriptionManager>) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            transcription_manager,
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Starts a session, returning the sender the recorder should feed segments into.
+    /// The session ends once that sender is dropped.
+    pub fn start(&self) -> mpsc::Sender<Vec<f32>> {
+        self.cancel();
+
+        let (segment_tx, segment_rx) = mpsc::channel::<Vec<f32>>();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let app_handle = self.app_handle.clone();
+        let tm = Arc::clone(&self.transcription_manager);
+        let worker_cancelled = Arc::clone(&cancelled);
+        let worker = thread::spawn(move || {
+            transcribe_segments(&app_handle, &tm, segment_rx, &worker_cancelled)
+        });
+
+        *self.session.lock().unwrap() = Some(StreamingSession { worker, cancelled });
+        debug!("Streaming transcription session started");
+        segment_tx
+    }
+
+    /// Waits for the remaining segments and returns the raw output for the
+    /// whole recording, with segment timings measured from its start, or `None`
+    /// if there was no session or a segment failed.
+    pub fn finish(&self) -> Option<TranscriptionOutput> {
+        let session = self.session.lock().unwrap().take()?;
+        match session.worker.join() {
+            Ok(output) => output,
+            Err(_) => {
+                warn!("Streaming transcription worker panicked");
+                None
+            }
+        }
+    }
+
+    /// Abandons the current session without waiting for it
+    pub fn cancel(&self) {
+        if let Some(session) = self.session.lock().unwrap().take() {
+            session.cancelled.store(true, Ordering::Relaxed);
+            debug!("Streaming transcription session cancelled");
+        }
+    }
+}
+
+fn transcribe_segments(
+    app_handle: &AppHandle,
+    tm: &TranscriptionManager,
+    segment_rx: mpsc::Receiver<Vec<f32>>,
+    cancelled: &AtomicBool,
+) -> Option<TranscriptionOutput> {
+    let mut output = TranscriptionOutput::default();
+    let mut texts = Vec::new();
+    // Segments arrive in order and cover the recording without gaps
+    let mut recorded_samples = 0;
+    let mut failed = false;
+
+    for mut samples in segment_rx {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let offset_ms = recorded_samples as u64 * 1000 / WHISPER_SAMPLE_RATE as u64;
+        recorded_samples += samples.len();
+        if failed {
+            // Keep draining; the full recording is transcribed on stop instead
+            continue;
+        }
+
+        // Pad very short segments the same way short recordings are padded
+        let min_len = WHISPER_SAMPLE_RATE as usize;
+        if samples.len() < min_len {
+            samples.resize(min_len * 5 / 4, 0.0);
+        }
+
+        match tm.transcribe_segment_detailed(samples) {
+            Ok(mut segment) => {
+                if segment.text.is_empty() {
+                    continue;
+                }
+                segment.shift(offset_ms);
+                texts.push(segment.text);
+                output.segments.extend(segment.segments);
+
+                let partial = tm.finalize_transcription(&texts.join(" "));
+                let _ = app_handle.emit(
+                    "partial-transcription",
+                    PartialTranscriptionEvent { text: partial },
+                );
+            }
+            Err(e) => {
+                warn!(
+                    "Streaming segment failed, will transcribe the full recording instead: {}",
+                    e
+                );
+                failed = true;
+            }
+        }
+    }
+
+    output.text = texts.join(" ");
+    (!failed && !cancelled.load(Ordering::Relaxed)).then_some(output)
+}
@@ -1,15 +1,25 @@
-use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::vad::{split_on_silence, SmoothedVad};
+use crate::audio_toolkit::{
+    apply_custom_words, apply_inverse_text_normalization, apply_replacement_rules,
+    filler_words_for_language, filter_transcription_output, format_spoken_commands, SileroVad,
+};
+use crate::managers::history::timings::group_words;
+use crate::managers::history::{TranscriptionMetadata, TranscriptionOutput, TranscriptionSegment};
 use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
-use anyhow::Result;
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
+use crate::transcription_coordinator::StageName;
+use crate::TranscriptionCoordinator;
+use anyhow::{bail, Result};
 use log::{debug, error, info, warn};
 use serde::Serialize;
+use std::ops::{ControlFlow, Range};
 use std::panic::{catch_unwind, AssertUnwindSafe};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
 use std::thread;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager, Runtime, Wry};
 use transcribe_rs::{
     engines::{
         moonshine::{
@@ -36,6 +46,9 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
+/// Longest piece of audio handed to the engine by [`TranscriptionManager::transcribe_long_form`]
+const MAX_SEGMENT_SECONDS: usize = 30;
+
 enum LoadedEngine {
     Whisper(WhisperEngine),
     Parakeet(ParakeetEngine),
@@ -44,23 +57,57 @@ enum LoadedEngine {
     SenseVoice(SenseVoiceEngine),
 }
 
-#[derive(Clone)]
-pub struct TranscriptionManager {
-    engine: Arc<Mutex<Option<LoadedEngine>>>,
-    model_manager: Arc<ModelManager>,
-    app_handle: AppHandle,
+/// The loaded engine, which a transcription takes out while it runs
+#[derive(Default)]
+struct EngineSlot {
+    engine: Option<LoadedEngine>,
+    /// Set while a transcription has the engine; it is put back afterwards
+    in_use: bool,
+}
+
+pub struct TranscriptionManager<R: Runtime = Wry> {
+    engine: Arc<Mutex<EngineSlot>>,
+    /// Notified when a transcription puts the engine back
+    engine_returned: Arc<Condvar>,
+    model_manager: Arc<ModelManager<R>>,
+    app_handle: AppHandle<R>,
     current_model_id: Arc<Mutex<Option<String>>>,
     last_activity: Arc<AtomicU64>,
     shutdown_signal: Arc<AtomicBool>,
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    /// Held while [`Self::with_model`] has another model loaded
+    model_switch: Arc<Mutex<()>>,
+    /// Number of [`Self::hold_model`] jobs running
+    held_jobs: Arc<AtomicUsize>,
+}
+
+// Not derived, since that would require `R: Clone`
+impl<R: Runtime> Clone for TranscriptionManager<R> {
+    fn clone(&self) -> Self {
+        Self {
+            engine: self.engine.clone(),
+            engine_returned: self.engine_returned.clone(),
+            model_manager: self.model_manager.clone(),
+            app_handle: self.app_handle.clone(),
+            current_model_id: self.current_model_id.clone(),
+            last_activity: self.last_activity.clone(),
+            shutdown_signal: self.shutdown_signal.clone(),
+            watcher_handle: self.watcher_handle.clone(),
+            is_loading: self.is_loading.clone(),
+            loading_condvar: self.loading_condvar.clone(),
+            model_switch: self.model_switch.clone(),
+            held_jobs: self.held_jobs.clone(),
+        }
+    }
 }
 
-impl TranscriptionManager {
-    pub fn new(app_handle: &AppHandle, model_manager: Arc<ModelManager>) -> Result<Self> {
+impl<R: Runtime> TranscriptionManager<R> {
+    pub fn new(app_handle: &AppHandle<R>, model_manager: Arc<ModelManager<R>>) -> Result<Self> {
         let manager = Self {
-            engine: Arc::new(Mutex::new(None)),
+            engine: Arc::new(Mutex::new(EngineSlot::default())),
+            engine_returned: Arc::new(Condvar::new()),
             model_manager,
             app_handle: app_handle.clone(),
             current_model_id: Arc::new(Mutex::new(None)),
@@ -74,6 +121,8 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            model_switch: Arc::new(Mutex::new(())),
+            held_jobs: Arc::new(AtomicUsize::new(0)),
         };
 
         // Start the idle watcher
@@ -140,16 +189,28 @@ impl TranscriptionManager {
     }
 
     /// Lock the engine mutex, recovering from poison if a previous transcription panicked.
-    fn lock_engine(&self) -> MutexGuard<'_, Option<LoadedEngine>> {
+    fn lock_engine(&self) -> MutexGuard<'_, EngineSlot> {
         self.engine.lock().unwrap_or_else(|poisoned| {
             warn!("Engine mutex was poisoned by a previous panic, recovering");
             poisoned.into_inner()
         })
     }
 
+    /// Lock the engine mutex once no transcription is using the engine.
+    fn lock_idle_engine(&self) -> MutexGuard<'_, EngineSlot> {
+        let mut slot = self.lock_engine();
+        while slot.in_use {
+            slot = self
+                .engine_returned
+                .wait(slot)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        slot
+    }
+
     pub fn is_model_loaded(&self) -> bool {
-        let engine = self.lock_engine();
-        engine.is_some()
+        let slot = self.lock_engine();
+        slot.engine.is_some() || slot.in_use
     }
 
     pub fn unload_model(&self) -> Result<()> {
@@ -157,8 +218,8 @@ impl TranscriptionManager {
         debug!("Starting to unload model");
 
         {
-            let mut engine = self.lock_engine();
-            if let Some(ref mut loaded_engine) = *engine {
+            let mut slot = self.lock_idle_engine();
+            if let Some(ref mut loaded_engine) = slot.engine {
                 match loaded_engine {
                     LoadedEngine::Whisper(ref mut e) => e.unload_model(),
                     LoadedEngine::Parakeet(ref mut e) => e.unload_model(),
@@ -167,7 +228,7 @@ impl TranscriptionManager {
                     LoadedEngine::SenseVoice(ref mut e) => e.unload_model(),
                 }
             }
-            *engine = None; // Drop the engine to free memory
+            slot.engine = None; // Drop the engine to free memory
         }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
@@ -198,6 +259,7 @@ impl TranscriptionManager {
         let settings = get_settings(&self.app_handle);
         if settings.model_unload_timeout == ModelUnloadTimeout::Immediately
             && self.is_model_loaded()
+            && self.held_jobs.load(Ordering::SeqCst) == 0
         {
             info!("Immediately unloading model after {}", context);
             if let Err(e) = self.unload_model() {
@@ -207,6 +269,14 @@ impl TranscriptionManager {
     }
 
     pub fn load_model(&self, model_id: &str) -> Result<()> {
+        let result = self.try_load_model(model_id);
+        // Keep the failure next to the model so it can be shown per model
+        self.model_manager
+            .set_load_error(model_id, result.as_ref().err().map(|e| e.to_string()));
+        result
+    }
+
+    fn try_load_model(&self, model_id: &str) -> Result<()> {
         let load_start = std::time::Instant::now();
         debug!("Starting to load model: {}", model_id);
 
@@ -350,8 +420,8 @@ impl TranscriptionManager {
 
         // Update the current engine and model ID
         {
-            let mut engine = self.lock_engine();
-            *engine = Some(loaded_engine);
+            let mut slot = self.lock_idle_engine();
+            slot.engine = Some(loaded_engine);
         }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
@@ -403,16 +473,110 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
-        // Update last activity timestamp
-        self.last_activity.store(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            Ordering::Relaxed,
+    /// Runs `job` with `model_id` loaded, then loads the previous model back
+    /// (or unloads it if none was loaded). Switching is refused while a
+    /// dictation is in progress, and dictations that start during the job
+    /// wait for the previous model to be restored. Jobs that need a switch
+    /// run one at a time.
+    pub fn with_model<T>(&self, model_id: &str, job: impl FnOnce() -> Result<T>) -> Result<T> {
+        let switch = self
+            .model_switch
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let previous = self.get_current_model();
+        if previous.as_deref() == Some(model_id) {
+            drop(switch);
+            return job();
+        }
+        if !self.dictation_idle() {
+            bail!("Can't switch models while a dictation is in progress");
+        }
+        if self.held_jobs.load(Ordering::SeqCst) > 0 {
+            bail!("Can't switch models while files are being transcribed");
+        }
+
+        self.load_model(model_id)?;
+        let result = job();
+
+        // A dictation that started meanwhile needs the selected model
+        let restore = previous.or_else(|| {
+            (!self.dictation_idle()).then(|| get_settings(&self.app_handle).selected_model)
+        });
+        let restored = match restore {
+            Some(previous) => self.load_model(&previous),
+            None => self.unload_model(),
+        };
+        if let Err(e) = restored {
+            error!(
+                "Failed to restore the model after using {}: {}",
+                model_id, e
+            );
+        }
+        result
+    }
+
+    /// Runs `job` with the current model kept loaded: [`Self::with_model`]
+    /// refuses to switch models meanwhile, and immediate unloading waits
+    /// until the job is done. A switch that is already running finishes first.
+    pub fn hold_model<T>(&self, job: impl FnOnce() -> Result<T>) -> Result<T> {
+        {
+            let _switch = self
+                .model_switch
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            self.held_jobs.fetch_add(1, Ordering::SeqCst);
+        }
+        let result = job();
+        self.held_jobs.fetch_sub(1, Ordering::SeqCst);
+
+        self.maybe_unload_immediately("held transcription");
+        result
+    }
+
+    /// Whether the dictation pipeline is idle, so the loaded model can change
+    fn dictation_idle(&self) -> bool {
+        self.app_handle
+            .try_state::<TranscriptionCoordinator>()
+            .and_then(|coordinator| coordinator.status().ok())
+            .is_none_or(|status| status.stage == StageName::Idle)
+    }
+
+    /// Blocks while [`Self::with_model`] has another model loaded
+    fn wait_for_model_switch(&self) {
+        drop(
+            self.model_switch
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
         );
+    }
+
+    /// Current settings, transcribing in `language` if given
+    fn settings_for(&self, language: Option<&str>) -> AppSettings {
+        let mut settings = get_settings(&self.app_handle);
+        if let Some(language) = language {
+            settings.selected_language = language.to_string();
+        }
+        settings
+    }
+
+    /// Model and engine of the current model and the language in `settings`,
+    /// for history metadata
+    fn metadata_for(&self, settings: &AppSettings) -> TranscriptionMetadata {
+        let model_id = self.get_current_model();
+        let engine_type = model_id
+            .as_deref()
+            .and_then(|id| self.model_manager.get_model_info(id))
+            .map(|info| format!("{:?}", info.engine_type));
+
+        TranscriptionMetadata {
+            model_id,
+            engine_type,
+            language: Some(settings.selected_language.clone()),
+            ..Default::default()
+        }
+    }
 
+    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
         let st = std::time::Instant::now();
 
         debug!("Audio vector length: {}", audio.len());
@@ -423,40 +587,283 @@ impl TranscriptionManager {
             return Ok(String::new());
         }
 
-        // Check if model is loaded, if not try to load it
+        let raw_text = self.transcribe_segment(audio)?;
+        let settings = get_settings(&self.app_handle);
+        let final_result = self.finalize_with(&raw_text, &settings);
+
+        let et = std::time::Instant::now();
+        let translation_note = if settings.translate_to_english {
+            " (translated)"
+        } else {
+            ""
+        };
+        info!(
+            "Transcription completed in {}ms{}",
+            (et - st).as_millis(),
+            translation_note
+        );
+
+        if final_result.is_empty() {
+            info!("Transcription result is empty");
+        } else {
+            info!("Transcription result: {}", final_result);
+        }
+
+        self.maybe_unload_immediately("transcription");
+
+        Ok(final_result)
+    }
+
+    /// Transcribes `audio` in one engine call, keeping the segment timings the
+    /// engine reports. Text goes through the same clean-up as
+    /// [`Self::transcribe`]; each segment's text is cleaned up on its own.
+    ///
+    /// This is the dictation path, so the engine runs exactly as it does for
+    /// [`Self::transcribe`]; word timings are left to [`Self::transcribe_long_form`].
+    pub fn transcribe_detailed(&self, audio: Vec<f32>) -> Result<TranscriptionOutput> {
+        let st = std::time::Instant::now();
+
+        self.wait_for_model_switch();
+        let settings = get_settings(&self.app_handle);
+        if audio.is_empty() {
+            let output = TranscriptionOutput {
+                metadata: self.metadata_for(&settings),
+                ..Default::default()
+            };
+            self.maybe_unload_immediately("empty audio");
+            return Ok(output);
+        }
+        let output = self.finalize_output(
+            self.transcribe_segment_timed(audio, false, &settings)?,
+            &settings,
+        );
+        info!(
+            "Detailed transcription with {} segments completed in {}ms",
+            output.segments.len(),
+            st.elapsed().as_millis()
+        );
+
+        self.maybe_unload_immediately("transcription");
+
+        Ok(output)
+    }
+
+    /// Transcribes audio of any length by splitting it at pauses and running the
+    /// engine on one segment at a time, like [`Self::transcribe_detailed`].
+    /// Engines that can report word timings are asked for them, since file
+    /// transcriptions are exported and stored with them.
+    ///
+    /// `language` overrides the configured language for this call only.
+    /// `on_progress` is called with the percentage of audio processed after each
+    /// segment; returning `ControlFlow::Break` stops with an error.
+    pub fn transcribe_long_form(
+        &self,
+        audio: &[f32],
+        language: Option<&str>,
+        mut on_progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<TranscriptionOutput> {
+        let st = std::time::Instant::now();
+        let settings = self.settings_for(language);
+
+        if audio.is_empty() {
+            let output = TranscriptionOutput {
+                metadata: self.metadata_for(&settings),
+                ..Default::default()
+            };
+            self.maybe_unload_immediately("empty audio");
+            return Ok(output);
+        }
+
+        let ranges = self.split_for_transcription(audio);
+        debug!(
+            "Split {} samples into {} segments",
+            audio.len(),
+            ranges.len()
+        );
+
+        let mut raw_texts = Vec::new();
+        let mut raw = TranscriptionOutput::default();
+        for range in ranges {
+            let mut samples = audio[range.clone()].to_vec();
+            let min_len = WHISPER_SAMPLE_RATE as usize;
+            if samples.len() < min_len {
+                samples.resize(min_len * 5 / 4, 0.0);
+            }
+
+            let mut chunk = self.transcribe_segment_timed(samples, true, &settings)?;
+            chunk.shift(samples_to_ms(range.start));
+            if !chunk.text.is_empty() {
+                raw_texts.push(chunk.text);
+            }
+            raw.segments.extend(chunk.segments);
+            if let Some(words) = chunk.words {
+                raw.words.get_or_insert_with(Vec::new).extend(words);
+            }
+
+            let percent = range.end as f32 / audio.len() as f32 * 100.0;
+            if on_progress(percent).is_break() {
+                self.maybe_unload_immediately("cancelled transcription");
+                return Err(anyhow::anyhow!("Transcription cancelled"));
+            }
+        }
+        raw.text = raw_texts.join(" ");
+
+        let output = self.finalize_output(raw, &settings);
+        info!(
+            "Long-form transcription with {} segments completed in {}ms",
+            output.segments.len(),
+            st.elapsed().as_millis()
+        );
+
+        self.maybe_unload_immediately("transcription");
+
+        Ok(output)
+    }
+
+    /// Splits `audio` into segments the engine can handle in one call, at pauses
+    /// found by the Silero VAD. Falls back to fixed-length segments if the VAD
+    /// cannot be loaded.
+    fn split_for_transcription(&self, audio: &[f32]) -> Vec<Range<usize>> {
+        let max_samples = MAX_SEGMENT_SECONDS * WHISPER_SAMPLE_RATE as usize;
+        if audio.len() <= max_samples {
+            return vec![0..audio.len()];
+        }
+
+        let split = self
+            .app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))
+            .and_then(|vad_path| SileroVad::new(vad_path, 0.3))
+            .and_then(|silero| {
+                let mut vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
+                split_on_silence(audio, &mut vad, max_samples)
+            });
+
+        match split {
+            Ok(ranges) => ranges,
+            Err(e) => {
+                warn!(
+                    "VAD segmentation failed, splitting at fixed intervals: {}",
+                    e
+                );
+                (0..audio.len())
+                    .step_by(max_samples)
+                    .map(|start| start..(start + max_samples).min(audio.len()))
+                    .collect()
+            }
+        }
+    }
+
+    /// Runs the loaded engine on `audio` and returns its raw output, before the
+    /// text clean-up done by [`Self::finalize_transcription`].
+    pub fn transcribe_segment(&self, audio: Vec<f32>) -> Result<String> {
+        self.wait_for_model_switch();
+        Ok(self
+            .run_engine(audio, false, &get_settings(&self.app_handle))?
+            .text)
+    }
+
+    /// Like [`Self::transcribe_segment`], but keeps the segment timings the
+    /// engine reports, relative to the start of `audio`.
+    pub fn transcribe_segment_detailed(&self, audio: Vec<f32>) -> Result<TranscriptionOutput> {
+        self.wait_for_model_switch();
+        self.transcribe_segment_timed(audio, false, &get_settings(&self.app_handle))
+    }
+
+    /// Like [`Self::transcribe_segment`], but keeps the engine's timestamps.
+    /// Audio without any timestamps becomes a single segment. With
+    /// `word_timestamps`, engines that support it also report per-word timings.
+    fn transcribe_segment_timed(
+        &self,
+        audio: Vec<f32>,
+        word_timestamps: bool,
+        settings: &AppSettings,
+    ) -> Result<TranscriptionOutput> {
+        let duration_ms = samples_to_ms(audio.len());
+        let result = self.run_engine(audio, word_timestamps, settings)?;
+
+        let timed: Vec<TranscriptionSegment> = result
+            .segments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|segment| TranscriptionSegment {
+                start_ms: seconds_to_ms(segment.start),
+                end_ms: seconds_to_ms(segment.end),
+                text: segment.text.trim().to_string(),
+            })
+            .filter(|segment| !segment.text.is_empty())
+            .collect();
+
+        let (mut segments, words) = if word_timestamps && self.engine_reports_words() {
+            (group_words(&timed), Some(timed))
+        } else {
+            (timed, None)
+        };
+
+        let text = result.text.trim().to_string();
+        if segments.is_empty() && !text.is_empty() {
+            segments.push(TranscriptionSegment {
+                start_ms: 0,
+                end_ms: duration_ms,
+                text: text.clone(),
+            });
+        }
+
+        Ok(TranscriptionOutput {
+            text,
+            segments,
+            words,
+            ..Default::default()
+        })
+    }
+
+    /// Runs the loaded engine on `audio` with the language and translation
+    /// options in `settings`. With `word_timestamps`, engines that support it
+    /// report one segment per word.
+    fn run_engine(
+        &self,
+        audio: Vec<f32>,
+        word_timestamps: bool,
+        settings: &AppSettings,
+    ) -> Result<transcribe_rs::TranscriptionResult> {
+        // Update last activity timestamp
+        self.last_activity.store(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            Ordering::Relaxed,
+        );
+
+        // If the model is loading, wait for it to complete.
         {
-            // If the model is loading, wait for it to complete.
             let mut is_loading = self.is_loading.lock().unwrap();
             while *is_loading {
                 is_loading = self.loading_condvar.wait(is_loading).unwrap();
             }
-
-            let engine_guard = self.lock_engine();
-            if engine_guard.is_none() {
-                return Err(anyhow::anyhow!("Model is not loaded for transcription."));
-            }
         }
 
-        // Get current settings for configuration
-        let settings = get_settings(&self.app_handle);
-
         // Perform transcription with the appropriate engine.
         // We use catch_unwind to prevent engine panics from poisoning the mutex,
         // which would make the app hang indefinitely on subsequent operations.
         let result = {
-            let mut engine_guard = self.lock_engine();
+            // Wait for any other transcription to put the engine back
+            let mut engine_guard = self.lock_idle_engine();
 
             // Take the engine out so we own it during transcription.
             // If the engine panics, we simply don't put it back (effectively unloading it)
             // instead of poisoning the mutex.
-            let mut engine = match engine_guard.take() {
+            let mut engine = match engine_guard.engine.take() {
                 Some(e) => e,
                 None => {
-                    return Err(anyhow::anyhow!(
-                        "Model failed to load after auto-load attempt. Please check your model settings."
-                    ));
+                    return Err(anyhow::anyhow!("Model is not loaded for transcription."));
                 }
             };
+            engine_guard.in_use = true;
 
             // Release the lock before transcribing — no mutex held during the engine call
             drop(engine_guard);
@@ -489,8 +896,13 @@ impl TranscriptionManager {
                                 .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))
                         }
                         LoadedEngine::Parakeet(parakeet_engine) => {
+                            let timestamp_granularity = if word_timestamps {
+                                TimestampGranularity::Word
+                            } else {
+                                TimestampGranularity::Segment
+                            };
                             let params = ParakeetInferenceParams {
-                                timestamp_granularity: TimestampGranularity::Segment,
+                                timestamp_granularity,
                                 ..Default::default()
                             };
                             parakeet_engine
@@ -534,12 +946,17 @@ impl TranscriptionManager {
                 Ok(inner_result) => {
                     // Success or normal error — put the engine back
                     let mut engine_guard = self.lock_engine();
-                    *engine_guard = Some(engine);
+                    engine_guard.engine = Some(engine);
+                    engine_guard.in_use = false;
+                    drop(engine_guard);
+                    self.engine_returned.notify_all();
                     inner_result?
                 }
                 Err(panic_payload) => {
                     // Engine panicked — do NOT put it back (it's in an unknown state).
                     // The engine is dropped here, effectively unloading it.
+                    self.lock_engine().in_use = false;
+                    self.engine_returned.notify_all();
                     let panic_msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
                         s.to_string()
                     } else if let Some(s) = panic_payload.downcast_ref::<String>() {
@@ -579,47 +996,128 @@ impl TranscriptionManager {
             }
         };
 
+        Ok(result)
+    }
+
+    /// Applies [`Self::finalize_transcription`] to the full text and to each
+    /// segment. Word timings keep the engine's raw words. Metadata is taken
+    /// now, before the model may be unloaded.
+    fn finalize_output(
+        &self,
+        raw: TranscriptionOutput,
+        settings: &AppSettings,
+    ) -> TranscriptionOutput {
+        TranscriptionOutput {
+            text: self.finalize_with(&raw.text, settings),
+            segments: raw
+                .segments
+                .into_iter()
+                .map(|segment| TranscriptionSegment {
+                    text: self.finalize_with(&segment.text, settings),
+                    ..segment
+                })
+                .collect(),
+            words: raw.words,
+            metadata: self.metadata_for(settings),
+        }
+    }
+
+    /// Applies custom words, filler filtering, ITN, spoken commands and
+    /// replacement rules to raw engine output.
+    pub fn finalize_transcription(&self, raw_text: &str) -> String {
+        self.finalize_with(raw_text, &get_settings(&self.app_handle))
+    }
+
+    /// Like [`Self::finalize_transcription`], for the text and segments of raw
+    /// output, and records the model that produced it.
+    pub fn finalize_detailed(&self, raw: TranscriptionOutput) -> TranscriptionOutput {
+        self.finalize_output(raw, &get_settings(&self.app_handle))
+    }
+
+    fn finalize_with(&self, raw_text: &str, settings: &AppSettings) -> String {
         // Apply word correction if custom words are configured
         let corrected_result = if !settings.custom_words.is_empty() {
             apply_custom_words(
-                &result.text,
+                raw_text,
                 &settings.custom_words,
                 settings.word_correction_threshold,
             )
         } else {
-            result.text
+            raw_text.to_string()
         };
 
-        // Filter out filler words and hallucinations
-        let filtered_result = filter_transcription_output(&corrected_result);
+        // Language-specific text handling follows the transcription language,
+        // falling back to the UI language when it is auto-detected
+        let text_language: &str = if settings.translate_to_english {
+            "en"
+        } else if settings.selected_language == "auto" {
+            &settings.app_language
+        } else {
+            &settings.selected_language
+        };
 
-        let et = std::time::Instant::now();
-        let translation_note = if settings.translate_to_english {
-            " (translated)"
+        // Filter out filler words and hallucinations
+        let filler_words: &[String] = if settings.filter_filler_words {
+            filler_words_for_language(&settings.filler_words, text_language)
         } else {
-            ""
+            &[]
         };
-        info!(
-            "Transcription completed in {}ms{}",
-            (et - st).as_millis(),
-            translation_note
+        let filtered_result = filter_transcription_output(
+            &corrected_result,
+            filler_words,
+            settings.collapse_stutters,
         );
 
-        let final_result = filtered_result;
+        // Write spoken numbers, dates and amounts in written form
+        let filtered_result =
+            if settings.inverse_text_normalization_enabled && !self.engine_applies_itn() {
+                apply_inverse_text_normalization(&filtered_result, text_language)
+            } else {
+                filtered_result
+            };
 
-        if final_result.is_empty() {
-            info!("Transcription result is empty");
+        // Turn dictated commands ("comma", "new line") into punctuation and layout
+        let filtered_result = if settings.spoken_punctuation_enabled {
+            format_spoken_commands(&filtered_result, text_language)
         } else {
-            info!("Transcription result: {}", final_result);
+            filtered_result
+        };
+
+        // Apply find/replace rules last so their output (e.g. newlines) isn't
+        // normalized away by the filter
+        if !settings.replacement_rules.is_empty() {
+            apply_replacement_rules(&filtered_result, &settings.replacement_rules)
+        } else {
+            filtered_result
         }
+    }
 
-        self.maybe_unload_immediately("transcription");
+    /// Whether the current engine already writes numbers, dates and currency
+    /// in written form
+    fn engine_applies_itn(&self) -> bool {
+        self.get_current_model()
+            .and_then(|id| self.model_manager.get_model_info(&id))
+            .is_some_and(|info| matches!(info.engine_type, EngineType::SenseVoice))
+    }
 
-        Ok(final_result)
+    /// Whether the current engine reports one timestamped segment per word
+    /// when asked for word timestamps
+    fn engine_reports_words(&self) -> bool {
+        self.get_current_model()
+            .and_then(|id| self.model_manager.get_model_info(&id))
+            .is_some_and(|info| matches!(info.engine_type, EngineType::Parakeet))
     }
 }
 
-impl Drop for TranscriptionManager {
+fn samples_to_ms(samples: usize) -> u64 {
+    samples as u64 * 1000 / WHISPER_SAMPLE_RATE as u64
+}
+
+fn seconds_to_ms(seconds: f32) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+impl<R: Runtime> Drop for TranscriptionManager<R> {
     fn drop(&mut self) {
         debug!("Shutting down TranscriptionManager");
 
@@ -2,11 +2,13 @@
 // This file is copied over transcription.rs during CI tests.
 // Existing tests don't exercise transcription, so this is safe.
 
+use crate::managers::history::TranscriptionOutput;
 use crate::managers::model::ModelManager;
 use anyhow::Result;
 use serde::Serialize;
+use std::ops::ControlFlow;
 use std::sync::Arc;
-use tauri::AppHandle;
+use tauri::{AppHandle, Runtime, Wry};
 
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
@@ -16,14 +18,21 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
-#[derive(Clone)]
-pub struct TranscriptionManager {
+pub struct TranscriptionManager<R: Runtime = Wry> {
     #[allow(dead_code)]
-    app_handle: AppHandle,
+    app_handle: AppHandle<R>,
 }
 
-impl TranscriptionManager {
-    pub fn new(app_handle: &AppHandle, _model_manager: Arc<ModelManager>) -> Result<Self> {
+impl<R: Runtime> Clone for TranscriptionManager<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app_handle: self.app_handle.clone(),
+        }
+    }
+}
+
+impl<R: Runtime> TranscriptionManager<R> {
+    pub fn new(app_handle: &AppHandle<R>, _model_manager: Arc<ModelManager<R>>) -> Result<Self> {
         Ok(Self {
             app_handle: app_handle.clone(),
         })
@@ -49,7 +58,44 @@ impl TranscriptionManager {
         None
     }
 
+    pub fn with_model<T>(&self, _model_id: &str, job: impl FnOnce() -> Result<T>) -> Result<T> {
+        job()
+    }
+
+    pub fn hold_model<T>(&self, job: impl FnOnce() -> Result<T>) -> Result<T> {
+        job()
+    }
+
     pub fn transcribe(&self, _audio: Vec<f32>) -> Result<String> {
         Ok(String::new())
     }
+
+    pub fn transcribe_detailed(&self, _audio: Vec<f32>) -> Result<TranscriptionOutput> {
+        Ok(TranscriptionOutput::default())
+    }
+
+    pub fn transcribe_long_form(
+        &self,
+        _audio: &[f32],
+        _language: Option<&str>,
+        _on_progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<TranscriptionOutput> {
+        Ok(TranscriptionOutput::default())
+    }
+
+    pub fn transcribe_segment(&self, _audio: Vec<f32>) -> Result<String> {
+        Ok(String::new())
+    }
+
+    pub fn transcribe_segment_detailed(&self, _audio: Vec<f32>) -> Result<TranscriptionOutput> {
+        Ok(TranscriptionOutput::default())
+    }
+
+    pub fn finalize_transcription(&self, raw_text: &str) -> String {
+        raw_text.to_string()
+    }
+
+    pub fn finalize_detailed(&self, raw: TranscriptionOutput) -> TranscriptionOutput {
+        raw
+    }
 }
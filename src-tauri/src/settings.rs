@@ -1,9 +1,12 @@
+use crate::audio_toolkit::text::{
+    DEFAULT_FILLER_WORDS, DEFAULT_GERMAN_FILLER_WORDS, DEFAULT_JAPANESE_FILLER_WORDS,
+};
 use log::{debug, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
 use std::collections::HashMap;
-use tauri::AppHandle;
+use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
@@ -105,6 +108,38 @@ pub struct PostProcessProvider {
     pub supports_structured_output: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementMatchKind {
+    Literal,
+    Regex,
+}
+
+impl Default for ReplacementMatchKind {
+    fn default() -> Self {
+        ReplacementMatchKind::Literal
+    }
+}
+
+/// A deterministic find/replace applied to every transcription.
+///
+/// `replacement` may contain `\n` and `\t` escapes; regex rules can also refer
+/// to capture groups as `$1` or `${name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct ReplacementRule {
+    pub id: String,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub match_kind: ReplacementMatchKind,
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
 pub enum OverlayPosition {
@@ -314,6 +349,16 @@ pub struct AppSettings {
     #[serde(default)]
     pub custom_words: Vec<String>,
     #[serde(default)]
+    pub rejected_correction_suggestions: Vec<String>,
+    #[serde(default)]
+    pub replacement_rules: Vec<ReplacementRule>,
+    #[serde(default = "default_true")]
+    pub filter_filler_words: bool,
+    #[serde(default = "default_filler_words")]
+    pub filler_words: HashMap<String, Vec<String>>,
+    #[serde(default = "default_true")]
+    pub collapse_stutters: bool,
+    #[serde(default)]
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
@@ -347,6 +392,30 @@ pub struct AppSettings {
     pub mute_while_recording: bool,
     #[serde(default)]
     pub append_trailing_space: bool,
+    #[serde(default)]
+    pub spoken_punctuation_enabled: bool,
+    #[serde(default)]
+    pub inverse_text_normalization_enabled: bool,
+    #[serde(default)]
+    pub streaming_transcription_enabled: bool,
+    #[serde(default)]
+    pub api_server_enabled: bool,
+    #[serde(default = "default_api_server_port")]
+    pub api_server_port: u16,
+    #[serde(default)]
+    pub api_server_token: Option<String>,
+    #[serde(default)]
+    pub transcription_webhook_url: Option<String>,
+    #[serde(default)]
+    pub transcription_hook_command: Option<String>,
+    #[serde(default = "default_transcription_hook_timeout_seconds")]
+    pub transcription_hook_timeout_seconds: u64,
+    #[serde(default)]
+    pub model_catalog_url: Option<String>,
+    #[serde(default)]
+    pub models_dir: Option<String>,
+    #[serde(default)]
+    pub model_search_paths: Vec<String>,
     #[serde(default = "default_app_language")]
     pub app_language: String,
     #[serde(default)]
@@ -362,6 +431,10 @@ pub struct AppSettings {
     pub external_script_path: Option<String>,
 }
 
+fn default_true() -> bool {
+    true
+}
+
 fn default_model() -> String {
     "".to_string()
 }
@@ -405,6 +478,22 @@ fn default_log_level() -> LogLevel {
     LogLevel::Debug
 }
 
+fn default_filler_words() -> HashMap<String, Vec<String>> {
+    [
+        ("en", DEFAULT_FILLER_WORDS),
+        ("de", DEFAULT_GERMAN_FILLER_WORDS),
+        ("ja", DEFAULT_JAPANESE_FILLER_WORDS),
+    ]
+    .into_iter()
+    .map(|(language, words)| {
+        (
+            language.to_string(),
+            words.iter().map(|w| w.to_string()).collect(),
+        )
+    })
+    .collect()
+}
+
 fn default_word_correction_threshold() -> f64 {
     0.18
 }
@@ -447,6 +536,14 @@ fn default_show_tray_icon() -> bool {
     true
 }
 
+fn default_api_server_port() -> u16 {
+    8765
+}
+
+fn default_transcription_hook_timeout_seconds() -> u64 {
+    10
+}
+
 fn default_post_process_provider_id() -> String {
     "openai".to_string()
 }
@@ -700,6 +797,11 @@ pub fn get_default_settings() -> AppSettings {
         debug_mode: false,
         log_level: default_log_level(),
         custom_words: Vec::new(),
+        rejected_correction_suggestions: Vec::new(),
+        replacement_rules: Vec::new(),
+        filter_filler_words: true,
+        filler_words: default_filler_words(),
+        collapse_stutters: true,
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
         history_limit: default_history_limit(),
@@ -717,6 +819,18 @@ pub fn get_default_settings() -> AppSettings {
         post_process_selected_prompt_id: None,
         mute_while_recording: false,
         append_trailing_space: false,
+        spoken_punctuation_enabled: false,
+        inverse_text_normalization_enabled: false,
+        streaming_transcription_enabled: false,
+        api_server_enabled: false,
+        api_server_port: default_api_server_port(),
+        api_server_token: None,
+        transcription_webhook_url: None,
+        transcription_hook_command: None,
+        transcription_hook_timeout_seconds: default_transcription_hook_timeout_seconds(),
+        model_catalog_url: None,
+        models_dir: None,
+        model_search_paths: Vec::new(),
         app_language: default_app_language(),
         experimental_enabled: false,
         keyboard_implementation: KeyboardImplementation::default(),
@@ -750,7 +864,7 @@ impl AppSettings {
     }
 }
 
-pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
+pub fn load_or_create_app_settings<R: Runtime>(app: &AppHandle<R>) -> AppSettings {
     // Initialize store
     let store = app
         .store(SETTINGS_STORE_PATH)
@@ -801,7 +915,7 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
     settings
 }
 
-pub fn get_settings(app: &AppHandle) -> AppSettings {
+pub fn get_settings<R: Runtime>(app: &AppHandle<R>) -> AppSettings {
     let store = app
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
@@ -825,7 +939,7 @@ pub fn get_settings(app: &AppHandle) -> AppSettings {
     settings
 }
 
-pub fn write_settings(app: &AppHandle, settings: AppSettings) {
+pub fn write_settings<R: Runtime>(app: &AppHandle<R>, settings: AppSettings) {
     let store = app
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
@@ -833,13 +947,13 @@ pub fn write_settings(app: &AppHandle, settings: AppSettings) {
     store.set("settings", serde_json::to_value(&settings).unwrap());
 }
 
-pub fn get_bindings(app: &AppHandle) -> HashMap<String, ShortcutBinding> {
+pub fn get_bindings<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, ShortcutBinding> {
     let settings = get_settings(app);
 
     settings.bindings
 }
 
-pub fn get_stored_binding(app: &AppHandle, id: &str) -> ShortcutBinding {
+pub fn get_stored_binding<R: Runtime>(app: &AppHandle<R>, id: &str) -> ShortcutBinding {
     let bindings = get_bindings(app);
 
     let binding = bindings.get(id).unwrap().clone();
@@ -847,12 +961,12 @@ pub fn get_stored_binding(app: &AppHandle, id: &str) -> ShortcutBinding {
     binding
 }
 
-pub fn get_history_limit(app: &AppHandle) -> usize {
+pub fn get_history_limit<R: Runtime>(app: &AppHandle<R>) -> usize {
     let settings = get_settings(app);
     settings.history_limit
 }
 
-pub fn get_recording_retention_period(app: &AppHandle) -> RecordingRetentionPeriod {
+pub fn get_recording_retention_period<R: Runtime>(app: &AppHandle<R>) -> RecordingRetentionPeriod {
     let settings = get_settings(app);
     settings.recording_retention_period
 }
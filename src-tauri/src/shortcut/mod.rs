@@ -16,12 +16,18 @@ mod tauri_impl;
 use log::{error, info, warn};
 use serde::Serialize;
 use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
+use crate::api_server::ApiServer;
+use crate::audio_toolkit::build_replacement_regex;
+use crate::managers::model::ModelManager;
+use crate::managers::model_store::ModelsDirMigration;
 use crate::settings::{
     self, get_settings, AutoSubmitKey, ClipboardHandling, KeyboardImplementation, LLMPrompt,
-    OverlayPosition, PasteMethod, ShortcutBinding, SoundTheme, TypingTool,
+    OverlayPosition, PasteMethod, ReplacementRule, ShortcutBinding, SoundTheme, TypingTool,
     APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
 };
 use crate::tray;
@@ -646,6 +652,20 @@ pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), Str
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn update_replacement_rules(app: AppHandle, rules: Vec<ReplacementRule>) -> Result<(), String> {
+    for rule in &rules {
+        build_replacement_regex(rule)
+            .map_err(|e| format!("Invalid pattern '{}': {}", rule.pattern, e))?;
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.replacement_rules = rules;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_word_correction_threshold_setting(
@@ -1040,6 +1060,221 @@ pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Re
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_filter_filler_words_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.filter_filler_words = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_filler_words(
+    app: AppHandle,
+    language: String,
+    words: Vec<String>,
+) -> Result<(), String> {
+    let language = language.trim();
+    if language.is_empty() {
+        return Err("Language code cannot be empty".to_string());
+    }
+    let words: Vec<String> = words
+        .iter()
+        .map(|w| w.trim().to_string())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut settings = settings::get_settings(&app);
+    settings.filler_words.insert(language.to_string(), words);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_collapse_stutters_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.collapse_stutters = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_spoken_punctuation_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.spoken_punctuation_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_inverse_text_normalization_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.inverse_text_normalization_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_streaming_transcription_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.streaming_transcription_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_api_server_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.api_server_enabled = enabled;
+    settings::write_settings(&app, settings);
+
+    // Start or stop the server immediately
+    app.state::<Arc<ApiServer>>().apply_settings()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_api_server_port_setting(app: AppHandle, port: u16) -> Result<(), String> {
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.api_server_port = port;
+    settings::write_settings(&app, settings);
+
+    // Move a running server to the new port
+    app.state::<Arc<ApiServer>>().apply_settings()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_api_server_token_setting(
+    app: AppHandle,
+    token: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.api_server_token = token.filter(|t| !t.trim().is_empty());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_webhook_url_setting(
+    app: AppHandle,
+    url: Option<String>,
+) -> Result<(), String> {
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err("Webhook URL must start with http:// or https://".to_string());
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_webhook_url = url;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_hook_command_setting(
+    app: AppHandle,
+    command: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_hook_command = command.filter(|c| !c.trim().is_empty());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_hook_timeout_setting(
+    app: AppHandle,
+    seconds: u64,
+) -> Result<(), String> {
+    if seconds == 0 {
+        return Err("Timeout must be at least one second".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_hook_timeout_seconds = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_model_catalog_url_setting(app: AppHandle, url: Option<String>) -> Result<(), String> {
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err("Catalog URL must start with http:// or https://".to_string());
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.model_catalog_url = url;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn change_models_dir_setting(
+    app: AppHandle,
+    path: Option<String>,
+    migration: ModelsDirMigration,
+) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    let new_dir = match &path {
+        Some(path) => PathBuf::from(path),
+        None => ModelManager::default_models_dir(&app).map_err(|e| e.to_string())?,
+    };
+
+    // Migrating can copy gigabytes between drives, keep it off the async runtime
+    let model_manager = app.state::<Arc<ModelManager>>().inner().clone();
+    tauri::async_runtime::spawn_blocking(move || model_manager.set_models_dir(new_dir, migration))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("{:#}", e))?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.models_dir = path;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_model_search_paths_setting(app: AppHandle, paths: Vec<String>) -> Result<(), String> {
+    let paths: Vec<String> = paths
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if let Some(relative) = paths.iter().find(|p| !Path::new(p).is_absolute()) {
+        return Err(format!("Search path must be absolute: {}", relative));
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.model_search_paths = paths.clone();
+    settings::write_settings(&app, settings);
+
+    app.state::<Arc<ModelManager>>()
+        .set_search_paths(paths.into_iter().map(PathBuf::from).collect())
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_app_language_setting(app: AppHandle, language: String) -> Result<(), String> {
@@ -1,13 +1,16 @@
 use crate::actions::ACTION_MAP;
 use crate::managers::audio::AudioRecordingManager;
 use log::{debug, error, warn};
-use std::sync::mpsc::{self, Sender};
+use serde::Serialize;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 
 const DEBOUNCE: Duration = Duration::from_millis(30);
+/// Passed to actions as the hotkey string for requests from control clients
+const CONTROL_SOURCE: &str = "control";
 
 /// Commands processed sequentially by the coordinator thread.
 enum Command {
@@ -21,15 +24,81 @@ enum Command {
         recording_was_active: bool,
     },
     ProcessingFinished,
+    /// Explicit start from a control client, answered once recording began
+    Start {
+        binding_id: String,
+        reply: Sender<Result<(), String>>,
+    },
+    Stop {
+        reply: Sender<Result<(), String>>,
+    },
+    Status(Sender<CoordinatorStatus>),
+    Transcript(Transcript),
+    LastTranscript(Sender<Option<Transcript>>),
+    Subscribe(Sender<CoordinatorEvent>),
 }
 
 /// Pipeline lifecycle, owned exclusively by the coordinator thread.
+#[derive(Clone, PartialEq)]
 enum Stage {
     Idle,
     Recording(String), // binding_id
     Processing,
 }
 
+impl Stage {
+    fn status(&self) -> CoordinatorStatus {
+        match self {
+            Stage::Idle => CoordinatorStatus {
+                stage: StageName::Idle,
+                binding_id: None,
+            },
+            Stage::Recording(id) => CoordinatorStatus {
+                stage: StageName::Recording,
+                binding_id: Some(id.clone()),
+            },
+            Stage::Processing => CoordinatorStatus {
+                stage: StageName::Processing,
+                binding_id: None,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageName {
+    Idle,
+    Recording,
+    Processing,
+}
+
+/// Snapshot of the pipeline stage, for clients outside the coordinator.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CoordinatorStatus {
+    pub stage: StageName,
+    /// Binding being recorded, while recording
+    pub binding_id: Option<String>,
+}
+
+/// A finished dictation.
+#[derive(Clone, Debug, Serialize)]
+pub struct Transcript {
+    pub binding_id: String,
+    pub text: String,
+    pub post_processed_text: Option<String>,
+    /// Unix time in seconds
+    pub timestamp: i64,
+}
+
+/// Pushed to subscribers whenever the stage changes or a dictation finishes.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoordinatorEvent {
+    StageChanged(CoordinatorStatus),
+    Transcript(Transcript),
+}
+
 /// Serialises all transcription lifecycle events through a single thread
 /// to eliminate race conditions between keyboard shortcuts, signals, and
 /// the async transcribe-paste pipeline.
@@ -49,8 +118,11 @@ impl TranscriptionCoordinator {
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 let mut stage = Stage::Idle;
                 let mut last_press: Option<Instant> = None;
+                let mut last_transcript: Option<Transcript> = None;
+                let mut subscribers: Vec<Sender<CoordinatorEvent>> = Vec::new();
 
                 while let Ok(cmd) = rx.recv() {
+                    let previous_stage = stage.clone();
                     match cmd {
                         Command::Input {
                             binding_id,
@@ -104,6 +176,52 @@ impl TranscriptionCoordinator {
                         Command::ProcessingFinished => {
                             stage = Stage::Idle;
                         }
+                        Command::Start { binding_id, reply } => {
+                            let result = if matches!(stage, Stage::Idle) {
+                                start(&app, &mut stage, &binding_id, CONTROL_SOURCE);
+                                if matches!(stage, Stage::Recording(_)) {
+                                    Ok(())
+                                } else {
+                                    Err("Recording did not start".to_string())
+                                }
+                            } else {
+                                Err("A transcription is already in progress".to_string())
+                            };
+                            let _ = reply.send(result);
+                        }
+                        Command::Stop { reply } => {
+                            let result = match stage.clone() {
+                                Stage::Recording(id) => {
+                                    stop(&app, &mut stage, &id, CONTROL_SOURCE);
+                                    Ok(())
+                                }
+                                _ => Err("Not recording".to_string()),
+                            };
+                            let _ = reply.send(result);
+                        }
+                        Command::Status(reply) => {
+                            let _ = reply.send(stage.status());
+                        }
+                        Command::Transcript(transcript) => {
+                            broadcast(
+                                &mut subscribers,
+                                CoordinatorEvent::Transcript(transcript.clone()),
+                            );
+                            last_transcript = Some(transcript);
+                        }
+                        Command::LastTranscript(reply) => {
+                            let _ = reply.send(last_transcript.clone());
+                        }
+                        Command::Subscribe(subscriber) => {
+                            subscribers.push(subscriber);
+                        }
+                    }
+
+                    if stage != previous_stage {
+                        broadcast(
+                            &mut subscribers,
+                            CoordinatorEvent::StageChanged(stage.status()),
+                        );
                     }
                 }
                 debug!("Transcription coordinator exited");
@@ -156,6 +274,60 @@ impl TranscriptionCoordinator {
             warn!("Transcription coordinator channel closed");
         }
     }
+
+    /// Record a finished dictation and pass it on to subscribers.
+    pub fn notify_transcript(&self, transcript: Transcript) {
+        if self.tx.send(Command::Transcript(transcript)).is_err() {
+            warn!("Transcription coordinator channel closed");
+        }
+    }
+
+    /// Start recording for `binding_id` if the pipeline is idle.
+    pub fn start_recording(&self, binding_id: &str) -> Result<(), String> {
+        self.request(|reply| Command::Start {
+            binding_id: binding_id.to_string(),
+            reply,
+        })?
+    }
+
+    /// Stop the current recording and transcribe it.
+    pub fn stop_recording(&self) -> Result<(), String> {
+        self.request(|reply| Command::Stop { reply })?
+    }
+
+    pub fn status(&self) -> Result<CoordinatorStatus, String> {
+        self.request(Command::Status)
+    }
+
+    /// The most recent dictation since the app started, if any.
+    pub fn last_transcript(&self) -> Result<Option<Transcript>, String> {
+        self.request(Command::LastTranscript)
+    }
+
+    /// Receive stage changes and transcripts until the receiver is dropped.
+    pub fn subscribe(&self) -> Receiver<CoordinatorEvent> {
+        let (tx, rx) = mpsc::channel();
+        if self.tx.send(Command::Subscribe(tx)).is_err() {
+            warn!("Transcription coordinator channel closed");
+        }
+        rx
+    }
+
+    /// Send a command carrying a reply channel and wait for the answer.
+    fn request<T>(&self, command: impl FnOnce(Sender<T>) -> Command) -> Result<T, String> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.tx
+            .send(command(reply_tx))
+            .map_err(|_| "Transcription coordinator is not running".to_string())?;
+        reply_rx
+            .recv()
+            .map_err(|_| "Transcription coordinator is not running".to_string())
+    }
+}
+
+/// Send `event` to every subscriber, dropping those that went away.
+fn broadcast(subscribers: &mut Vec<Sender<CoordinatorEvent>>, event: CoordinatorEvent) {
+    subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
 }
 
 fn start(app: &AppHandle, stage: &mut Stage, binding_id: &str, hotkey_string: &str) {
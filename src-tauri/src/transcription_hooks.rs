@@ -0,0 +1,268 @@
+//! Hooks fired after each dictation: an HTTP webhook and/or a local command,
+//! both receiving the same JSON payload.
+//!
+//! Hooks run in the background once the transcription has been saved, so a
+//! slow or broken hook never delays pasting. Failures are only logged.
+
+use crate::managers::history::{TranscriptionMetadata, TranscriptionSegment};
+use crate::settings::AppSettings;
+use log::{debug, error};
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// JSON body sent to the webhook and written to the command's stdin
+#[derive(Clone, Debug, Serialize)]
+pub struct TranscriptionHookPayload {
+    pub text: String,
+    pub post_processed_text: Option<String>,
+    /// Unix time in seconds when the transcription finished
+    pub timestamp: i64,
+    pub audio_duration_ms: Option<i64>,
+    /// Segment timings within the recording, for engines that report them
+    pub segments: Option<Vec<TranscriptionSegment>>,
+    pub model_id: Option<String>,
+    pub language: Option<String>,
+    pub binding_id: Option<String>,
+}
+
+impl TranscriptionHookPayload {
+    pub fn new(
+        text: String,
+        post_processed_text: Option<String>,
+        metadata: &TranscriptionMetadata,
+        segments: Option<Vec<TranscriptionSegment>>,
+    ) -> Self {
+        Self {
+            text,
+            post_processed_text,
+            timestamp: chrono::Utc::now().timestamp(),
+            audio_duration_ms: metadata.audio_duration_ms,
+            segments,
+            model_id: metadata.model_id.clone(),
+            language: metadata.language.clone(),
+            binding_id: metadata.binding_id.clone(),
+        }
+    }
+}
+
+/// Starts the configured hooks in the background and returns immediately.
+pub fn run_transcription_hooks(settings: &AppSettings, payload: TranscriptionHookPayload) {
+    let timeout = Duration::from_secs(settings.transcription_hook_timeout_seconds.max(1));
+
+    if let Some(url) = non_empty(&settings.transcription_webhook_url) {
+        let url = url.to_string();
+        let payload = payload.clone();
+        tauri::async_runtime::spawn(async move {
+            match send_webhook(&url, &payload, timeout).await {
+                Ok(()) => debug!("Transcription webhook delivered to {}", url),
+                Err(e) => error!("Transcription webhook failed: {}", e),
+            }
+        });
+    }
+
+    if let Some(command) = non_empty(&settings.transcription_hook_command) {
+        let command = command.to_string();
+        thread::spawn(move || match run_command(&command, &payload, timeout) {
+            Ok(()) => debug!("Transcription hook command finished"),
+            Err(e) => error!("Transcription hook command failed: {}", e),
+        });
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// POSTs the payload as JSON, failing on non-2xx responses
+pub async fn send_webhook(
+    url: &str,
+    payload: &TranscriptionHookPayload,
+    timeout: Duration,
+) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Request to {} failed: {}", url, e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("{} responded with {}", url, status));
+    }
+    Ok(())
+}
+
+/// Runs `command` through the platform shell with the payload on stdin,
+/// killing it if it outlives `timeout`
+pub fn run_command(
+    command: &str,
+    payload: &TranscriptionHookPayload,
+    timeout: Duration,
+) -> Result<(), String> {
+    let body = serde_json::to_vec(payload).map_err(|e| e.to_string())?;
+
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start '{}': {}", command, e))?;
+
+    // Written from its own thread: a payload larger than the pipe buffer
+    // blocks until the command reads it, which must not hold off the timeout.
+    // A command that ignores stdin may exit before reading it; that's fine
+    let deadline = Instant::now() + timeout;
+    if let Some(mut stdin) = child.stdin.take() {
+        thread::spawn(move || {
+            let _ = stdin.write_all(&body);
+        });
+    }
+
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(format!("'{}' exited with {}", command, status)),
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("'{}' timed out after {:?}", command, timeout));
+            }
+            None => thread::sleep(COMMAND_POLL_INTERVAL),
+        }
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    fn payload() -> TranscriptionHookPayload {
+        TranscriptionHookPayload {
+            text: "hello world".to_string(),
+            post_processed_text: Some("Hello, world!".to_string()),
+            timestamp: 1_700_000_000,
+            audio_duration_ms: Some(1500),
+            segments: None,
+            model_id: Some("small".to_string()),
+            language: Some("en".to_string()),
+            binding_id: Some("transcribe".to_string()),
+        }
+    }
+
+    /// Accepts one request, answers it with `status` and returns its body
+    fn serve_once(status: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            )
+            .unwrap();
+            String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn webhook_posts_payload_as_json() {
+        let (url, server) = serve_once("200 OK");
+        tauri::async_runtime::block_on(send_webhook(&url, &payload(), Duration::from_secs(5)))
+            .unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(body["text"], "hello world");
+        assert_eq!(body["post_processed_text"], "Hello, world!");
+        assert_eq!(body["model_id"], "small");
+        assert_eq!(body["binding_id"], "transcribe");
+    }
+
+    #[test]
+    fn webhook_reports_error_status() {
+        let (url, server) = serve_once("500 Internal Server Error");
+        let result =
+            tauri::async_runtime::block_on(send_webhook(&url, &payload(), Duration::from_secs(5)));
+        server.join().unwrap();
+        assert!(result.unwrap_err().contains("500"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_receives_payload_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.json");
+        let command = format!("cat > '{}'", out.display());
+        run_command(&command, &payload(), Duration::from_secs(5)).unwrap();
+
+        let body: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out).unwrap()).unwrap();
+        assert_eq!(body["text"], "hello world");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_is_killed_after_timeout() {
+        let start = Instant::now();
+        let result = run_command("sleep 5", &payload(), Duration::from_millis(200));
+        assert!(result.unwrap_err().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_applies_while_payload_is_unread() {
+        let mut payload = payload();
+        payload.text = "a".repeat(1024 * 1024);
+
+        let start = Instant::now();
+        let result = run_command("sleep 5", &payload, Duration::from_millis(200));
+        assert!(result.unwrap_err().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+}
@@ -181,10 +181,7 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
 }
 
 fn last_transcript_text(entry: &HistoryEntry) -> &str {
-    entry
-        .post_processed_text
-        .as_deref()
-        .unwrap_or(&entry.transcription_text)
+    entry.final_text()
 }
 
 pub fn set_tray_visibility(app: &AppHandle, visible: bool) {
@@ -233,6 +230,9 @@ mod tests {
             transcription_text: transcription.to_string(),
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
+            metadata: Default::default(),
+            edited_text: None,
+            timings: None,
         }
     }
 
@@ -242,6 +242,13 @@ mod tests {
         assert_eq!(last_transcript_text(&entry), "processed");
     }
 
+    #[test]
+    fn prefers_edited_text_over_post_processed() {
+        let mut entry = build_entry("raw", Some("processed"));
+        entry.edited_text = Some("edited".to_string());
+        assert_eq!(last_transcript_text(&entry), "edited");
+    }
+
     #[test]
     fn falls_back_to_raw_transcription() {
         let entry = build_entry("raw", None);
@@ -6,20 +6,28 @@ import { open } from "@tauri-apps/plugin-dialog";
 import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 
+interface TranscriptionSegment {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+}
+
 interface FileTranscriptionResult {
   text: string;
   file_name: string;
   duration_ms: number;
+  segments: TranscriptionSegment[];
 }
 
 interface FileTranscriptionProgress {
   stage: string;
   message: string | null;
+  percent: number | null;
 }
 
 type TranscribeState =
   | { kind: "idle" }
-  | { kind: "processing"; stage: string }
+  | { kind: "processing"; stage: string; percent: number | null }
   | { kind: "result"; result: FileTranscriptionResult }
   | { kind: "error"; message: string };
 
@@ -48,7 +56,7 @@ export const TranscribeSettings: React.FC = () => {
         return;
       }
 
-      setState({ kind: "processing", stage: "decoding" });
+      setState({ kind: "processing", stage: "decoding", percent: null });
 
       try {
         const result = await invoke<FileTranscriptionResult>(
@@ -81,7 +89,11 @@ export const TranscribeSettings: React.FC = () => {
       (event) => {
         setState((prev) => {
           if (prev.kind === "processing") {
-            return { kind: "processing", stage: event.payload.stage };
+            return {
+              kind: "processing",
+              stage: event.payload.stage,
+              percent: event.payload.percent,
+            };
           }
           return prev;
         });
@@ -177,7 +189,10 @@ export const TranscribeSettings: React.FC = () => {
             {state.kind === "processing" ? (
               <>
                 <Loader2 className="w-8 h-8 text-logo-primary animate-spin" />
-                <p className="text-sm text-mid-gray">{t(stageKey)}</p>
+                <p className="text-sm text-mid-gray">
+                  {t(stageKey)}
+                  {state.percent !== null && ` ${Math.round(state.percent)}%`}
+                </p>
               </>
             ) : (
               <>
@@ -13,11 +13,20 @@ import { getLanguageDirection } from "@/lib/utils/rtl";
 
 type OverlayState = "recording" | "transcribing" | "processing";
 
+// Only the tail of the partial transcription fits in the overlay
+const PARTIAL_TEXT_MAX_CHARS = 24;
+
+const tailOf = (text: string) =>
+  text.length > PARTIAL_TEXT_MAX_CHARS
+    ? `…${text.slice(-PARTIAL_TEXT_MAX_CHARS).trimStart()}`
+    : text;
+
 const RecordingOverlay: React.FC = () => {
   const { t } = useTranslation();
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
+  const [partialText, setPartialText] = useState("");
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
   const direction = getLanguageDirection(i18n.language);
 
@@ -28,6 +37,9 @@ const RecordingOverlay: React.FC = () => {
         // Sync language from settings each time overlay is shown
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
+        if (overlayState === "recording") {
+          setPartialText("");
+        }
         setState(overlayState);
         setIsVisible(true);
       });
@@ -51,11 +63,20 @@ const RecordingOverlay: React.FC = () => {
         setLevels(smoothed.slice(0, 9));
       });
 
+      // Listen for text recognised while still recording
+      const unlistenPartial = await listen<{ text: string }>(
+        "partial-transcription",
+        (event) => {
+          setPartialText(event.payload.text);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
+        unlistenPartial();
       };
     };
 
@@ -78,7 +99,10 @@ const RecordingOverlay: React.FC = () => {
       <div className="overlay-left">{getIcon()}</div>
 
       <div className="overlay-middle">
-        {state === "recording" && (
+        {state === "recording" && partialText && (
+          <div className="partial-text">{tailOf(partialText)}</div>
+        )}
+        {state === "recording" && !partialText && (
           <div className="bars-container">
             {levels.map((v, i) => (
               <div